@@ -7,6 +7,12 @@ export interface TestCase {
   indexContent: string;
   expectedReachable: string[];
   expectedUnused: Array<{ file: string; name: string }>;
+  expectedUnusedTypes?: Array<{ file: string; name: string }>;
+  expectedUsedInFile?: Array<{ file: string; name: string }>;
+  expectedUnusedFiles?: string[];
+  expectedUnusedDependencies?: Array<{ name: string; kind: string }>;
+  expectedUnlistedDependencies?: Array<{ name: string; packageJson: string; importers: string[] }>;
+  expectedUnresolvedImports?: Array<{ file: string; specifier: string; reason: string }>;
   config?: SweepyConfig
 }
 
@@ -17,30 +23,58 @@ export const testCases: TestCase[] = [
     fixture: 'basic-named',
     indexContent: '// no imports',
     expectedReachable: ['index.ts'],
+    expectedUnusedFiles: ['utils.ts'],
     expectedUnused: [
       { file: 'utils.ts', name: 'MyClass' },
       { file: 'utils.ts', name: 'MyEnum' },
-      { file: 'utils.ts', name: 'MyInterface' },
-      { file: 'utils.ts', name: 'MyType' },
       { file: 'utils.ts', name: 'bar' },
       { file: 'utils.ts', name: 'baz' },
       { file: 'utils.ts', name: 'foo' },
       { file: 'utils.ts', name: 'myFunction' },
     ],
+    expectedUnusedTypes: [
+      { file: 'utils.ts', name: 'MyInterface' },
+      { file: 'utils.ts', name: 'MyType' },
+    ],
   },
   {
     title: 'basic named exports - some imports',
     fixture: 'basic-named',
     indexContent: 'import { foo, bar } from "./utils";',
     expectedReachable: ['index.ts', 'utils.ts'],
+    expectedUnusedFiles: [],
     expectedUnused: [
       { file: 'utils.ts', name: 'MyClass' },
       { file: 'utils.ts', name: 'MyEnum' },
+      { file: 'utils.ts', name: 'baz' },
+      { file: 'utils.ts', name: 'myFunction' },
+    ],
+    expectedUnusedTypes: [
       { file: 'utils.ts', name: 'MyInterface' },
       { file: 'utils.ts', name: 'MyType' },
+    ],
+  },
+  {
+    title: 'basic named exports - unresolved imports',
+    fixture: 'basic-named',
+    indexContent: 'import { foo } from "./Utils";\nimport { bar } from "./missing";\nimport "./style.css";',
+    expectedReachable: ['index.ts'],
+    expectedUnused: [
+      { file: 'utils.ts', name: 'MyClass' },
+      { file: 'utils.ts', name: 'MyEnum' },
+      { file: 'utils.ts', name: 'bar' },
       { file: 'utils.ts', name: 'baz' },
+      { file: 'utils.ts', name: 'foo' },
       { file: 'utils.ts', name: 'myFunction' },
     ],
+    expectedUnusedTypes: [
+      { file: 'utils.ts', name: 'MyInterface' },
+      { file: 'utils.ts', name: 'MyType' },
+    ],
+    expectedUnresolvedImports: [
+      { file: 'index.ts', specifier: './Utils', reason: 'case-mismatch' },
+      { file: 'index.ts', specifier: './missing', reason: 'no-matching-file' },
+    ],
   },
   {
     title: 'basic named exports - all imports',
@@ -107,6 +141,20 @@ export const testCases: TestCase[] = [
     expectedUnused: [],
   },
 
+  // ===== Dynamic Imports =====
+  {
+    title: 'dynamic import - destructured members',
+    fixture: 'namespace-import',
+    indexContent: 'const { foo } = await import("./utils");',
+    expectedReachable: ['index.ts', 'utils.ts'],
+    expectedUnused: [
+      { file: 'utils.ts', name: 'MyClass' },
+      { file: 'utils.ts', name: 'bar' },
+      { file: 'utils.ts', name: 'baz' },
+      { file: 'utils.ts', name: 'myFunction' },
+    ],
+  },
+
   // ===== Re-export All =====
   {
     title: 'reexport all - no imports',
@@ -166,6 +214,56 @@ export const testCases: TestCase[] = [
       { file: 'utils.ts', name: 'qux' },
     ],
   },
+  {
+    title: 'reexport named - entry exports as public API',
+    fixture: 'reexport-named',
+    indexContent: 'export { foo } from "./barrel";',
+    expectedReachable: ['barrel.ts', 'index.ts', 'utils.ts'],
+    expectedUnused: [
+      { file: 'barrel.ts', name: 'bar' },
+      { file: 'barrel.ts', name: 'extra' },
+      { file: 'utils.ts', name: 'bar' },
+      { file: 'utils.ts', name: 'baz' },
+      { file: 'utils.ts', name: 'qux' },
+    ],
+    config: { publicEntryExports: true },
+  },
+
+  // ===== Dependencies =====
+  {
+    title: 'dependencies - some imported',
+    fixture: 'dependencies',
+    indexContent: 'import { run } from "./utils";',
+    expectedReachable: ['index.ts', 'utils.ts'],
+    expectedUnused: [],
+    expectedUnusedDependencies: [
+      { name: 'dayjs', kind: 'dependencies' },
+    ],
+  },
+  {
+    title: 'dependencies - only imported by unreachable files',
+    fixture: 'dependencies',
+    indexContent: '// no imports',
+    expectedReachable: ['index.ts'],
+    expectedUnused: [
+      { file: 'utils.ts', name: 'run' },
+    ],
+    expectedUnusedDependencies: [
+      { name: '@types/lodash', kind: 'devDependencies' },
+      { name: 'dayjs', kind: 'dependencies' },
+      { name: 'lodash', kind: 'dependencies' },
+    ],
+  },
+  {
+    title: 'dependencies - unlisted import',
+    fixture: 'dependencies',
+    indexContent: 'import { run } from "./utils";\nimport axios from "axios/dist/axios";',
+    expectedReachable: ['index.ts', 'utils.ts'],
+    expectedUnused: [],
+    expectedUnlistedDependencies: [
+      { name: 'axios', packageJson: 'package.json', importers: ['index.ts'] },
+    ],
+  },
 
   // ===== Deep Paths =====
   {
@@ -210,6 +308,16 @@ export const testCases: TestCase[] = [
       { file: 'utils.js', name: 'jsFunction' },
     ],
   },
+  {
+    title: 'commonjs - destructured require',
+    fixture: 'commonjs',
+    indexContent: 'const { greet } = require("./utils.cjs");',
+    expectedReachable: ['format.cjs', 'index.ts', 'utils.cjs'],
+    expectedUnused: [
+      { file: 'format.cjs', name: 'pad' },
+      { file: 'utils.cjs', name: 'farewell' },
+    ],
+  },
   {
     title: 'jsx import - import jsx component',
     fixture: 'jsx-import',
@@ -257,11 +365,13 @@ export const testCases: TestCase[] = [
     expectedReachable: ['Component.vue', 'index.ts', 'utils.ts'],
     expectedUnused: [
       { file: 'Component.vue', name: 'ComponentName' },
-      { file: 'utils.ts', name: 'ApiResponse' },
-      { file: 'utils.ts', name: 'User' },
       { file: 'utils.ts', name: 'formatDate' },
       { file: 'utils.ts', name: 'unusedUtilFunction' },
     ],
+    expectedUnusedTypes: [
+      { file: 'utils.ts', name: 'ApiResponse' },
+      { file: 'utils.ts', name: 'User' },
+    ],
   },
   {
     title: 'vue mixed - ts imports types from vue',
@@ -270,11 +380,13 @@ export const testCases: TestCase[] = [
     expectedReachable: ['Component.vue', 'index.ts', 'utils.ts'],
     expectedUnused: [
       { file: 'Component.vue', name: 'useComponent' },
-      { file: 'utils.ts', name: 'ApiResponse' },
-      { file: 'utils.ts', name: 'User' },
       { file: 'utils.ts', name: 'formatDate' },
       { file: 'utils.ts', name: 'unusedUtilFunction' },
     ],
+    expectedUnusedTypes: [
+      { file: 'utils.ts', name: 'ApiResponse' },
+      { file: 'utils.ts', name: 'User' },
+    ],
   },
   {
     title: 'vue chain - vue imports vue imports ts',
@@ -285,10 +397,30 @@ export const testCases: TestCase[] = [
       { file: 'App.vue', name: 'useApp' },
       { file: 'Child.vue', name: 'unusedChildExport' },
       { file: 'Child.vue', name: 'useChild' },
+      { file: 'api.ts', name: 'unusedApiFunction' },
+    ],
+    expectedUnusedTypes: [
       { file: 'api.ts', name: 'ApiConfig' },
+    ],
+    expectedUsedInFile: [
       { file: 'api.ts', name: 'config' },
+    ],
+  },
+  {
+    title: 'vue chain - ignore exports used in file',
+    fixture: 'vue-chain',
+    indexContent: 'import { App } from "./App.vue";',
+    expectedReachable: ['App.vue', 'Child.vue', 'api.ts', 'index.ts'],
+    expectedUnused: [
+      { file: 'App.vue', name: 'useApp' },
+      { file: 'Child.vue', name: 'unusedChildExport' },
+      { file: 'Child.vue', name: 'useChild' },
       { file: 'api.ts', name: 'unusedApiFunction' },
     ],
+    expectedUnusedTypes: [
+      { file: 'api.ts', name: 'ApiConfig' },
+    ],
+    config: { ignoreExportsUsedInFile: true },
   },
   {
     title: 'vue chain - import without extension',
@@ -300,9 +432,13 @@ export const testCases: TestCase[] = [
       { file: 'App.vue', name: 'useApp' },
       { file: 'Child.vue', name: 'unusedChildExport' },
       { file: 'Child.vue', name: 'useChild' },
+      { file: 'api.ts', name: 'unusedApiFunction' },
+    ],
+    expectedUnusedTypes: [
       { file: 'api.ts', name: 'ApiConfig' },
+    ],
+    expectedUsedInFile: [
       { file: 'api.ts', name: 'config' },
-      { file: 'api.ts', name: 'unusedApiFunction' },
     ],
   },
 
@@ -361,4 +497,16 @@ export const testCases: TestCase[] = [
       },
     },
   },
+
+  // ===== tsconfig Paths =====
+  {
+    title: 'tsconfig paths - wildcard and fallback targets',
+    fixture: 'tsconfig-paths',
+    indexContent: 'import { helper } from "@/utils";\nimport { format } from "#lib/format";',
+    expectedReachable: ['index.ts', path.join('src', 'utils.ts'), path.join('vendor', 'format.ts')],
+    expectedUnused: [
+      { file: path.join('src', 'utils.ts'), name: 'unused' },
+      { file: path.join('vendor', 'format.ts'), name: 'unusedFormat' },
+    ],
+  },
 ];
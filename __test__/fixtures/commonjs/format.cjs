@@ -0,0 +1,2 @@
+exports.format = (value) => value.trim();
+exports.pad = (value) => ` ${value} `;
@@ -0,0 +1,11 @@
+const { format } = require('./format.cjs');
+
+function greet(name) {
+  return format(`hello ${name}`);
+}
+
+function farewell(name) {
+  return format(`bye ${name}`);
+}
+
+module.exports = { greet, farewell };
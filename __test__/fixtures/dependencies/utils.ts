@@ -0,0 +1,4 @@
+import debounce from 'lodash/debounce';
+import { readFile } from 'node:fs/promises';
+
+export const run = debounce(() => readFile('data.json'), 100);
@@ -0,0 +1,2 @@
+export const helper = () => 'help'
+export const unused = 'unused'
@@ -1,17 +1,18 @@
 import test from 'ava'
 import path from 'node:path'
-import { sweepy } from '../index'
-import { writeFile, glob, mkdtemp, readFile, mkdir } from 'node:fs/promises'
+import { affectedEntries, exportGraph, findReferences, sweepy, whyReachable } from '../index'
+import { writeFile, glob, mkdtemp, readFile, mkdir, rm } from 'node:fs/promises'
 import os from 'node:os'
 import { testCases } from './cases'
 
-const SUPPORTED_EXTENSIONS = ['ts', 'tsx', 'js', 'jsx', 'vue']
+const SUPPORTED_EXTENSIONS = ['ts', 'tsx', 'js', 'jsx', 'vue', 'mts', 'cts', 'mjs', 'cjs']
+const FIXTURE_EXTENSIONS = [...SUPPORTED_EXTENSIONS, 'json']
 
 async function prepareTsProject({ name, indexContent }: { name: string; indexContent: string }) {
   const root = await mkdtemp(path.join(os.tmpdir(), 'sweepy-'))
   const tsProject = path.resolve(import.meta.dirname, 'fixtures', name)
 
-  const files = await Array.fromAsync(glob(`**/*.{${SUPPORTED_EXTENSIONS}}`, { cwd: tsProject }))
+  const files = await Array.fromAsync(glob(`**/*.{${FIXTURE_EXTENSIONS}}`, { cwd: tsProject }))
 
   await Promise.all(
     files.map(async (file) => {
@@ -38,9 +39,137 @@ for (const testCase of testCases) {
 
     const res = sweepy(root, [indexFile], testCase.config)
 
-    t.deepEqual(res, {
-      reachableFiles: testCase.expectedReachable,
-      unusedExports: testCase.expectedUnused,
-    })
+    t.deepEqual(res.reachableFiles, testCase.expectedReachable)
+    t.deepEqual(
+      res.unusedExports.map(({ file, name }) => ({ file, name })),
+      testCase.expectedUnused
+    )
+    t.deepEqual(
+      res.unusedTypes.map(({ file, name }) => ({ file, name })),
+      testCase.expectedUnusedTypes ?? []
+    )
+
+    t.deepEqual(
+      res.usedInFileExports.map(({ file, name }) => ({ file, name })),
+      testCase.expectedUsedInFile ?? []
+    )
+
+    if (testCase.expectedUnusedFiles) {
+      t.deepEqual(res.unusedFiles, testCase.expectedUnusedFiles)
+    }
+
+    if (testCase.expectedUnusedDependencies) {
+      t.deepEqual(res.unusedDependencies, testCase.expectedUnusedDependencies)
+    }
+
+    if (testCase.expectedUnlistedDependencies) {
+      t.deepEqual(res.unlistedDependencies, testCase.expectedUnlistedDependencies)
+    }
+
+    if (testCase.expectedUnresolvedImports) {
+      t.deepEqual(
+        res.unresolvedImports.map(({ file, specifier, reason }) => ({ file, specifier, reason })),
+        testCase.expectedUnresolvedImports
+      )
+    }
   })
 }
+
+test('whyReachable - import path through a barrel', async (t) => {
+  const { root, indexFile } = await prepareTsProject({
+    name: 'reexport-named',
+    indexContent: 'import { foo } from "./barrel";',
+  })
+
+  t.deepEqual(whyReachable(root, [indexFile], path.join(root, 'utils.ts')), [
+    {
+      entry: 'index.ts',
+      hops: [
+        { from: 'index.ts', to: 'barrel.ts', specifier: './barrel', kind: 'import', typeOnly: false },
+        { from: 'barrel.ts', to: 'utils.ts', specifier: './utils', kind: 're-export', typeOnly: false },
+      ],
+    },
+  ])
+})
+
+test('findReferences - through a barrel', async (t) => {
+  const { root } = await prepareTsProject({
+    name: 'reexport-named',
+    indexContent: 'import { foo } from "./barrel";',
+  })
+
+  t.deepEqual(findReferences(root, path.join(root, 'utils.ts'), 'foo'), [
+    { file: 'barrel.ts', line: 1, column: 10, kind: 're-export', specifier: './utils', name: 'foo', typeOnly: false },
+    { file: 'index.ts', line: 1, column: 21, kind: 'named', specifier: './barrel', name: 'foo', typeOnly: false },
+  ])
+})
+
+test('exportGraph - json adjacency lists', async (t) => {
+  const { root, indexFile } = await prepareTsProject({
+    name: 'reexport-named',
+    indexContent: 'import { foo } from "./barrel";',
+  })
+
+  t.deepEqual(JSON.parse(exportGraph(root, [indexFile])), {
+    nodes: {
+      'barrel.ts': {
+        files: 1,
+        reachable: true,
+        unusedExports: 2,
+        imports: [{ to: 'utils.ts', kinds: ['re-export'], typeOnly: false }],
+      },
+      'index.ts': {
+        files: 1,
+        reachable: true,
+        unusedExports: 0,
+        imports: [{ to: 'barrel.ts', kinds: ['import'], typeOnly: false }],
+      },
+      'utils.ts': { files: 1, reachable: true, unusedExports: 3, imports: [] },
+    },
+  })
+  t.throws(() => exportGraph(root, [indexFile], { format: 'svg' }))
+})
+
+test('exportGraph - unused export counts match sweepy', async (t) => {
+  const { root, indexFile } = await prepareTsProject({
+    name: 'reexport-named',
+    indexContent:
+      'import { foo } from "./barrel";\nexport { bar } from "./barrel";\nexport type Props = { size: number };\nexport const local = 1;\nconsole.log(local);',
+  })
+
+  for (const config of [{}, { publicEntryExports: true }, { ignoreExportsUsedInFile: true }]) {
+    const expected: Record<string, number> = {}
+    for (const { file } of sweepy(root, [indexFile], config).unusedExports) {
+      expected[file] = (expected[file] ?? 0) + 1
+    }
+
+    const { nodes } = JSON.parse(exportGraph(root, [indexFile], undefined, config))
+    const counts: Record<string, number> = {}
+    for (const [id, node] of Object.entries<{ unusedExports: number }>(nodes)) {
+      if (node.unusedExports > 0) counts[id] = node.unusedExports
+    }
+    t.deepEqual(counts, expected, JSON.stringify(config))
+  }
+})
+
+test('affectedEntries - importers of a changed file', async (t) => {
+  const { root, indexFile } = await prepareTsProject({
+    name: 'reexport-named',
+    indexContent: 'import { foo } from "./barrel";',
+  })
+
+  t.deepEqual(affectedEntries(root, [indexFile], [path.join(root, 'utils.ts')]), {
+    files: ['barrel.ts', 'index.ts', 'utils.ts'],
+    entries: ['index.ts'],
+  })
+  t.deepEqual(affectedEntries(root, [indexFile], [path.join(root, 'missing.ts')]), {
+    files: [],
+    entries: [],
+  })
+
+  await rm(path.join(root, 'utils.ts'))
+  t.deepEqual(affectedEntries(root, [indexFile], [path.join(root, 'utils.ts')]), {
+    files: ['barrel.ts', 'index.ts'],
+    entries: ['index.ts'],
+  })
+})
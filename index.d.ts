@@ -1,17 +1,214 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
+/**
+ * The files and entrypoints affected by a change to `changed_files`, following imports in
+ * reverse. Deleted files and assets affect the files whose imports would match them.
+ */
+export declare function affectedEntries(root: string, entries: Array<string>, changedFiles: Array<string>, config?: SweepyConfig | undefined | null): AffectedFiles
+
+/**
+ * Serialize the module graph as Graphviz DOT, a Mermaid flowchart or JSON adjacency lists.
+ * Nodes carry reachability from the entries and unused export counts, edges the import kinds.
+ */
+export declare function exportGraph(root: string, entries: Array<string>, options?: ExportGraphOptions | undefined | null, config?: SweepyConfig | undefined | null): string
+
+/**
+ * Every module consuming export `export_name` of `file`, following re-exports through
+ * barrels and namespace imports, sorted by file and position
+ */
+export declare function findReferences(root: string, file: string, exportName: string, config?: SweepyConfig | undefined | null): Array<ExportReference>
+
 export declare function sweepy(root: string, entries: Array<string>, config?: SweepyConfig | undefined | null): SweepyResult
 
+/**
+ * Explain why `file` is reachable: the shortest import path from an entrypoint to it, or
+ * up to `max_paths` of them, shortest first. Empty when the file is not reachable.
+ */
+export declare function whyReachable(root: string, entries: Array<string>, file: string, config?: SweepyConfig | undefined | null, maxPaths?: number | undefined | null): Array<ImportPath>
+
+/** What a change to a set of files affects */
+export interface AffectedFiles {
+  /** The changed files and every file importing them, directly or not, sorted */
+  files: Array<string>
+  /** Entrypoints among `files`, sorted */
+  entries: Array<string>
+}
+
+/** Files importing each other in a loop */
+export interface CircularDependency {
+  /** runtime, or type when the loop only closes through type-only imports */
+  kind: string
+  /** Every file of the strongly connected component */
+  files: Array<string>
+  /** A shortest loop through the files, the last one importing the first */
+  path: Array<CycleEdge>
+}
+
+/** A file in a cycle and how it imports the next one */
+export interface CycleEdge {
+  file: string
+  specifier: string
+  typeOnly: boolean
+}
+
+/** A package imported by source files, flagged against their nearest package.json */
+export interface DependencyImport {
+  name: string
+  /** The nearest package.json of the importing files */
+  packageJson: string
+  importers: Array<string>
+}
+
+export interface ExportGraphOptions {
+  /** dot, mermaid or json (the default) */
+  format?: string
+  /** Directories whose files merge into a single node */
+  collapseDirectories?: Array<string>
+  /** Only export the files this one reaches, itself included */
+  subtree?: string
+}
+
+/** A module consuming an export, directly or through re-exports */
+export interface ExportReference {
+  file: string
+  /** 1-based position of the import specifier, or of the re-exported name */
+  line: number
+  column: number
+  /** named, default, namespace, namespace-member or re-export */
+  kind: string
+  /** Specifier as written in `file` */
+  specifier: string
+  /** Name the export goes by in the module `specifier` points to */
+  name: string
+  typeOnly: boolean
+}
+
+/** One import along an import path, the runtime one when the files share several */
+export interface ImportHop {
+  from: string
+  to: string
+  /** Specifier `from` imports `to` with */
+  specifier: string
+  /** import, dynamic-import, require, import-equals, glob or re-export */
+  kind: string
+  typeOnly: boolean
+}
+
+/** A chain of imports from an entrypoint to a file */
+export interface ImportPath {
+  entry: string
+  /** Each import along the way, in order; empty when the file is the entrypoint */
+  hops: Array<ImportHop>
+}
+
+/** A name exported by an entrypoint treated as public API */
+export interface PublicExport {
+  entry: string
+  name: string
+  /** File declaring it, once re-exports are followed */
+  file: string
+  /** Name and position of the declaration in `file` */
+  local: string
+  kind: string
+  line: number
+  column: number
+}
+
 export interface SweepyConfig {
   alias?: Record<string, string>
+  /** Path to the tsconfig.json providing `baseUrl` and `paths`, defaults to `<root>/tsconfig.json` */
+  tsconfig?: string
+  unusedFiles?: UnusedFilesConfig
+  /** Only count imports made by files reachable from the entries when deciding export usage */
+  reachableImportsOnly?: boolean
+  /** Leave exports used inside their own file out of the results entirely */
+  ignoreExportsUsedInFile?: boolean
+  /** Treat exports of the entrypoints, and everything they re-export, as used public API */
+  publicEntryExports?: boolean
 }
 
 export interface SweepyResult {
   reachableFiles: Array<string>
+  unusedFiles: Array<string>
+  /** Unused value exports: functions, classes, variables, enums, namespaces and defaults */
   unusedExports: Array<UnusedExport>
+  /** Unused type aliases and interfaces, and type-only exports */
+  unusedTypes: Array<UnusedExport>
+  /** Value exports only used as types */
+  typeOnlyExports: Array<TypeOnlyExport>
+  /**
+   * Exports not imported anywhere but used inside their own file, which could be un-exported.
+   * They are left out of `unusedExports` and `unusedTypes`.
+   */
+  usedInFileExports: Array<UnusedExport>
+  /** Exports of the entrypoints when `publicEntryExports` is set, sorted by entry and name */
+  publicApi: Array<PublicExport>
+  /** Dependencies of the root package.json never imported by a reachable file */
+  unusedDependencies: Array<UnusedDependency>
+  /** Packages reachable files import that their nearest package.json does not declare */
+  unlistedDependencies: Array<DependencyImport>
+  /**
+   * Packages only in `devDependencies` but imported at runtime by reachable files that are
+   * not tests, config or declaration files
+   */
+  misplacedDependencies: Array<DependencyImport>
+  /** Relative and aliased imports of every file that match no project file */
+  unresolvedImports: Array<UnresolvedImport>
+  /** Import cycles, runtime ones first */
+  cycles: Array<CircularDependency>
+}
+
+/** A value export that is only ever used as a type, and could be `export type` */
+export interface TypeOnlyExport {
+  file: string
+  name: string
+  kind: string
+  line: number
+  column: number
+  /**
+   * Files importing it as a value while only using it in type positions, which could
+   * switch to `import type`
+   */
+  importers: Array<string>
+}
+
+/** A relative or aliased import that matches no project file */
+export interface UnresolvedImport {
+  file: string
+  specifier: string
+  /** no-matching-file, alias-target-missing, extension-mismatch or case-mismatch */
+  reason: string
+  /** 1-based position of the specifier */
+  line: number
+  column: number
+}
+
+/** A package.json dependency no reachable file imports */
+export interface UnusedDependency {
+  name: string
+  /** dependencies, devDependencies or peerDependencies */
+  kind: string
 }
 
 export interface UnusedExport {
   file: string
   name: string
+  /** function, class, variable, type, interface, enum, namespace, default or re-export */
+  kind: string
+  /** 1-based line of the exported name */
+  line: number
+  /** 1-based column of the exported name, in UTF-16 code units */
+  column: number
+  /** Byte offsets of the exported name in the file */
+  start: number
+  end: number
+}
+
+export interface UnusedFilesConfig {
+  /** Skip tool configuration files such as `vite.config.ts` or `.eslintrc.js` */
+  ignoreConfigFiles?: boolean
+  /** Skip `.d.ts` declaration files */
+  ignoreDeclarationFiles?: boolean
+  /** Skip `*.test.*`/`*.spec.*` files and files under `__tests__` or `__mocks__` */
+  ignoreTestFiles?: boolean
 }
@@ -1,54 +1,387 @@
+use crate::package_json::{DependencyKind, PackageJson};
+use crate::tsconfig::TsconfigPaths;
 use anyhow::Result;
+use globset::{GlobBuilder, GlobSetBuilder};
 use oxc_allocator::Allocator;
 use oxc_ast::ast::*;
+use oxc_ast_visit::{Visit, walk};
 use oxc_parser::Parser as OxcParser;
-use oxc_span::SourceType;
+use oxc_semantic::{ReferenceId, ScopeId, Semantic, SemanticBuilder};
+use oxc_span::{GetSpan, SourceType, Span};
 use path_clean::PathClean;
-use std::collections::{HashMap, HashSet};
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use vue_oxc_parser::parser::VueOxcParser;
 
 /// Supported file extensions for source files
-pub const SUPPORTED_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "vue"];
+pub const SUPPORTED_EXTENSIONS: &[&str] =
+  &["ts", "tsx", "js", "jsx", "vue", "mts", "cts", "mjs", "cjs"];
+
+/// Non-source files bundlers import, which never resolve to a project file
+const ASSET_EXTENSIONS: &[&str] = &[
+  "css", "scss", "sass", "less", "styl", "pcss", "svg", "png", "jpg", "jpeg", "gif", "webp",
+  "avif", "ico", "bmp", "woff", "woff2", "ttf", "otf", "eot", "mp4", "webm", "mp3", "wav", "ogg",
+  "json", "html", "md", "mdx", "txt", "wasm", "glsl", "graphql", "gql", "yaml", "yml", "toml",
+  "csv",
+];
+
+/// Node.js built-in modules, importable without the `node:` prefix
+const NODE_BUILTINS: &[&str] = &[
+  "assert",
+  "async_hooks",
+  "buffer",
+  "child_process",
+  "cluster",
+  "console",
+  "constants",
+  "crypto",
+  "dgram",
+  "diagnostics_channel",
+  "dns",
+  "domain",
+  "events",
+  "fs",
+  "http",
+  "http2",
+  "https",
+  "inspector",
+  "module",
+  "net",
+  "os",
+  "path",
+  "perf_hooks",
+  "process",
+  "punycode",
+  "querystring",
+  "readline",
+  "repl",
+  "stream",
+  "string_decoder",
+  "sys",
+  "timers",
+  "tls",
+  "trace_events",
+  "tty",
+  "url",
+  "util",
+  "v8",
+  "vm",
+  "wasi",
+  "worker_threads",
+  "zlib",
+];
 
 #[derive(Debug, Clone, Default)]
 pub struct SweepyConfig {
   pub alias: HashMap<String, String>,
+  pub tsconfig: Option<TsconfigPaths>,
+  /// package.json files keyed by their directory, used to resolve directory imports
+  pub packages: HashMap<PathBuf, PackageJson>,
+  pub unused_files: UnusedFilesOptions,
+  /// Entrypoints whose exports, and everything they re-export, are public API used by
+  /// consumers outside the project
+  pub public_entries: Vec<PathBuf>,
+}
+
+/// Kinds of files left out of the unused files report
+#[derive(Debug, Clone, Default)]
+pub struct UnusedFilesOptions {
+  pub ignore_config_files: bool,
+  pub ignore_declaration_files: bool,
+  pub ignore_test_files: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct ImportInfo {
-  pub source: String,          // module specifier as written: "./foo"
+  pub source: String, // module specifier as written: "./foo"
+  pub kind: ImportKind,
   pub specifiers: Vec<String>, // imported names; empty => likely a side-effect import or `import * as ns`
   pub has_namespace: bool,     // true if `import * as ns from ...`
   pub has_default: bool,       // true if `import def from ...`
+  /// Members read off the namespace object (`ns.foo`, `ns['foo']`, `const { foo } = ns`).
+  /// `None` when the namespace escapes, so every export counts as used.
+  pub namespace_members: Option<Vec<String>>,
+  pub type_only: bool, // true if `import type ... from ...`
+  /// Names imported as types only, through `import type` or an inline `type` modifier
+  pub type_specifiers: Vec<String>,
+  /// Names imported as values but only referenced in type positions
+  pub type_position_only: Vec<String>,
+}
+
+/// How a file imports another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+  Static,
+  Dynamic,
+  Require,
+  /// `import x = require('./x')`
+  ImportEquals,
+  /// `import.meta.glob()` or `require.context()`
+  Glob,
+  /// `export ... from`
+  ReExport,
+}
+
+impl ImportKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      ImportKind::Static => "import",
+      ImportKind::Dynamic => "dynamic-import",
+      ImportKind::Require => "require",
+      ImportKind::ImportEquals => "import-equals",
+      ImportKind::Glob => "glob",
+      ImportKind::ReExport => "re-export",
+    }
+  }
+}
+
+impl ImportInfo {
+  /// Whether the imported `name` is only needed by the type checker
+  fn is_type_use(&self, name: &str) -> bool {
+    self.type_only
+      || self.type_specifiers.iter().any(|s| s == name)
+      || self.type_position_only.iter().any(|s| s == name)
+  }
+
+  /// Whether the whole import is erased at runtime. Side-effect imports never are.
+  fn is_erased(&self) -> bool {
+    self.type_only
+      || (!self.has_namespace
+        && !self.has_default
+        && !self.specifiers.is_empty()
+        && self.specifiers.iter().all(|name| self.is_type_use(name)))
+  }
+}
+
+/// `import.meta.glob()` or `require.context()`, expanded against the project files
+#[derive(Debug, Clone)]
+pub struct GlobImport {
+  pub source: String, // first pattern or context directory as written
+  pub pattern: GlobPattern,
+  /// Exports read off each matched module, `None` when any may be
+  pub namespace_members: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone)]
+pub enum GlobPattern {
+  /// Vite globs, `!` prefixed ones excluding matches
+  Glob(Vec<String>),
+  /// webpack context: files under `directory` whose `./`-prefixed relative path matches `regex`
+  Context {
+    directory: String,
+    recursive: bool,
+    regex: String,
+  },
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct ExportInfo {
   pub name: String,
+  pub local: String, // binding it refers to: the name in `source` for re-exports, else the local binding
   pub source: Option<PathBuf>,
+  pub kind: ExportKind,
+  pub type_only: bool, // true if `export type { ... }` or an inline `type` specifier
+  pub span: Span,      // span of the exported name, or of the `export default` statement
+  pub line: u32,       // 1-based, filled from `span` once the module is extracted
+  pub column: u32,     // 1-based, in UTF-16 code units like editors count them
+  /// The local binding is referenced in its own module, outside its declaration
+  pub used_in_file: bool,
+}
+
+impl ExportInfo {
+  /// Declared as a type here, without following re-exports
+  fn declares_type(&self) -> bool {
+    self.type_only || matches!(self.kind, ExportKind::Type | ExportKind::Interface)
+  }
+}
+
+/// What an export declares
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportKind {
+  Function,
+  Class,
+  #[default]
+  Variable,
+  Type,
+  Interface,
+  Enum,
+  Namespace,
+  Default,
+  ReExport,
+}
+
+impl ExportKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      ExportKind::Function => "function",
+      ExportKind::Class => "class",
+      ExportKind::Variable => "variable",
+      ExportKind::Type => "type",
+      ExportKind::Interface => "interface",
+      ExportKind::Enum => "enum",
+      ExportKind::Namespace => "namespace",
+      ExportKind::Default => "default",
+      ExportKind::ReExport => "re-export",
+    }
+  }
+}
+
+/// Why a relative or aliased specifier matches no project file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnresolvedReason {
+  NoMatchingFile,
+  /// An alias or tsconfig `paths` pattern matched, but none of its targets exist
+  AliasTargetMissing,
+  /// A file exists with another extension: `./Button.tsx` next to `Button.vue`
+  ExtensionMismatch,
+  /// A file only matches case-insensitively, which breaks on case-sensitive file systems
+  CaseMismatch,
+}
+
+impl UnresolvedReason {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      UnresolvedReason::NoMatchingFile => "no-matching-file",
+      UnresolvedReason::AliasTargetMissing => "alias-target-missing",
+      UnresolvedReason::ExtensionMismatch => "extension-mismatch",
+      UnresolvedReason::CaseMismatch => "case-mismatch",
+    }
+  }
 }
 
 #[derive(Debug, Clone)]
 pub enum ExportItem {
   Named(ExportInfo),
+  /// `export * as ns from './mod'`, with `local` set to `*`
+  Namespace(ExportInfo),
   All(PathBuf),
 }
 
+impl ExportItem {
+  /// Module specifier this export forwards from, if any
+  pub fn source(&self) -> Option<&PathBuf> {
+    match self {
+      ExportItem::Named(exp) | ExportItem::Namespace(exp) => exp.source.as_ref(),
+      ExportItem::All(source) => Some(source),
+    }
+  }
+}
+
 #[derive(Debug, Clone)]
 pub struct ParsedFile {
   pub imports: Vec<ImportInfo>,
   pub exports: Vec<ExportItem>,
+  pub globs: Vec<GlobImport>,
+  /// Exports come from `module.exports`/`exports`, whose default import is the whole object
+  pub is_commonjs: bool,
+  /// 1-based line and UTF-16 column of the first occurrence of every module specifier
+  pub specifier_positions: HashMap<String, (u32, u32)>,
+}
+
+/// An import of one project file by another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportEdge {
+  /// The specifier the importing file resolves to the target with
+  pub specifier: String,
+  /// How `specifier` is imported
+  pub kind: ImportKind,
+  /// The import or re-export is erased at runtime
+  pub type_only: bool,
+}
+
+/// The import that keeps an edge alive: the first runtime one, else the first type-only one
+fn primary_import(imports: &[ImportEdge]) -> &ImportEdge {
+  imports
+    .iter()
+    .find(|import| !import.type_only)
+    .unwrap_or(&imports[0])
+}
+
+/// How a module consumes an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReferenceKind {
+  /// `import { name }`, or a destructured dynamic import or require
+  Named,
+  Default,
+  /// A namespace object that escapes, so any export may be read off it
+  Namespace,
+  /// `ns.name` on a namespace import
+  NamespaceMember,
+  /// A barrel forwarding it, under its own or another name
+  ReExport,
+}
+
+impl ReferenceKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      ReferenceKind::Named => "named",
+      ReferenceKind::Default => "default",
+      ReferenceKind::Namespace => "namespace",
+      ReferenceKind::NamespaceMember => "namespace-member",
+      ReferenceKind::ReExport => "re-export",
+    }
+  }
+}
+
+/// A module consuming an export, directly or through re-exports
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Reference {
+  pub file: PathBuf,
+  /// 1-based position of the import specifier, or of the re-exported name
+  pub line: u32,
+  pub column: u32,
+  pub kind: ReferenceKind,
+  /// Specifier as written in `file`
+  pub specifier: String,
+  /// Name the export goes by in the module `specifier` points to
+  pub name: String,
+  pub type_only: bool,
+}
+
+/// Files importing each other in a loop
+#[derive(Debug, Clone)]
+pub struct ImportCycle {
+  /// The loop only closes through type-only imports
+  pub type_only: bool,
+  /// Every file of the strongly connected component, sorted
+  pub files: Vec<PathBuf>,
+  /// A shortest loop through the component: each file with its edge to the next one,
+  /// the last file importing the first
+  pub path: Vec<(PathBuf, ImportEdge)>,
 }
 
 pub struct ProjectAnalyzer {
   pub files: HashMap<PathBuf, ParsedFile>,
   pub graph: HashMap<PathBuf, HashSet<PathBuf>>,
+  /// Every distinct import behind each `graph` edge, with its specifier and kind
+  pub edges: HashMap<PathBuf, HashMap<PathBuf, Vec<ImportEdge>>>,
   pub import_usage: HashMap<PathBuf, Vec<(PathBuf, ImportInfo)>>,
+  /// Resolved target of every import/re-export specifier, per importing file
+  pub resolved: HashMap<PathBuf, HashMap<String, PathBuf>>,
+  /// Packages imported through bare specifiers per importing file, mapped to whether the
+  /// file only imports types from them
+  pub external: HashMap<PathBuf, HashMap<String, bool>>,
   pub config: SweepyConfig,
 }
 
+/// Which exports are used, once imports are followed through re-export chains
+#[derive(Debug, Default)]
+struct ExportUsage {
+  used: HashSet<(PathBuf, String)>,
+  /// Modules whose every export is used, e.g. through `import * as ns`
+  fully_used: HashSet<PathBuf>,
+}
+
+impl ExportUsage {
+  fn is_used(&self, module: &Path, name: &str) -> bool {
+    self.fully_used.contains(module)
+      || self
+        .used
+        .contains(&(module.to_path_buf(), name.to_string()))
+  }
+}
+
 impl ProjectAnalyzer {
   pub fn from_sources(
     sources: &HashMap<PathBuf, &str>,
@@ -68,69 +401,118 @@ impl ProjectAnalyzer {
 
     let mut graph: HashMap<PathBuf, HashSet<PathBuf>> = HashMap::new();
     let mut import_usage: HashMap<PathBuf, Vec<(PathBuf, ImportInfo)>> = HashMap::new();
+    let mut resolved: HashMap<PathBuf, HashMap<String, PathBuf>> = HashMap::new();
+    let mut external: HashMap<PathBuf, HashMap<String, bool>> = HashMap::new();
+    let mut edges: HashMap<PathBuf, HashMap<PathBuf, Vec<ImportEdge>>> = HashMap::new();
+    let mut add_edge = |from: &PathBuf, to: &PathBuf, edge: ImportEdge| {
+      let imports = edges
+        .entry(from.clone())
+        .or_default()
+        .entry(to.clone())
+        .or_default();
+      if !imports.contains(&edge) {
+        imports.push(edge);
+      }
+    };
 
     for (path, pf) in &files {
+      let mut resolve = |spec: &str, kind: ImportKind, type_only: bool| -> Option<PathBuf> {
+        let Some(target) = resolve_relative_import_from_set(path, spec, &file_set, &config) else {
+          if let Some(name) = package_name(spec, &config) {
+            let only_types = external
+              .entry(path.clone())
+              .or_default()
+              .entry(name)
+              .or_insert(true);
+            *only_types &= type_only;
+          }
+          return None;
+        };
+        graph
+          .entry(path.clone())
+          .or_default()
+          .insert(target.clone());
+        add_edge(
+          path,
+          &target,
+          ImportEdge {
+            specifier: spec.to_string(),
+            kind,
+            type_only,
+          },
+        );
+        resolved
+          .entry(path.clone())
+          .or_default()
+          .insert(spec.to_string(), target.clone());
+        Some(target)
+      };
+
       for imp in &pf.imports {
-        if let Some(target) =
-          resolve_relative_import_from_set(path, &imp.source, &file_set, &config.alias)
-        {
-          graph
-            .entry(path.clone())
-            .or_default()
-            .insert(target.clone());
+        if let Some(target) = resolve(&imp.source, imp.kind, imp.is_erased()) {
           import_usage
-            .entry(target.clone())
+            .entry(target)
             .or_default()
             .push((path.clone(), imp.clone()));
         }
       }
 
       for export in &pf.exports {
-        match export {
-          ExportItem::All(specifier_path) => {
-            if let Some(spec) = specifier_path.to_str()
-              && let Some(target) =
-                resolve_relative_import_from_set(path, spec, &file_set, &config.alias)
-            {
-              graph
-                .entry(path.clone())
-                .or_default()
-                .insert(target.clone());
-              true
-            } else {
-              false
-            }
-          }
-          ExportItem::Named(exp) => {
-            if let Some(src) = &exp.source {
-              if let Some(spec) = src.to_str()
-                && let Some(target) =
-                  resolve_relative_import_from_set(path, spec, &file_set, &config.alias)
-              {
-                graph
-                  .entry(path.clone())
-                  .or_default()
-                  .insert(target.clone());
-                true
-              } else {
-                false
-              }
-            } else {
-              false
-            }
-          }
-        };
+        let type_only = matches!(export, ExportItem::Named(exp) if exp.type_only);
+        if let Some(spec) = export.source().and_then(|s| s.to_str()) {
+          resolve(spec, ImportKind::ReExport, type_only);
+        }
+      }
+
+      for glob in &pf.globs {
+        for target in expand_glob(path, glob, &file_set, &config) {
+          graph
+            .entry(path.clone())
+            .or_default()
+            .insert(target.clone());
+          add_edge(
+            path,
+            &target,
+            ImportEdge {
+              specifier: glob.source.clone(),
+              kind: ImportKind::Glob,
+              type_only: false,
+            },
+          );
+          import_usage.entry(target).or_default().push((
+            path.clone(),
+            ImportInfo {
+              source: glob.source.clone(),
+              kind: ImportKind::Glob,
+              specifiers: Vec::new(),
+              has_namespace: true,
+              has_default: false,
+              namespace_members: glob.namespace_members.clone(),
+              type_only: false,
+              type_specifiers: Vec::new(),
+              type_position_only: Vec::new(),
+            },
+          ));
+        }
       }
     }
 
     Ok(Self {
       files,
       graph,
+      edges,
       import_usage,
+      resolved,
+      external,
       config,
     })
   }
 
+  /// Resolved target of a specifier written in `from`
+  fn resolve(&self, from: &Path, spec: &Path) -> Option<&PathBuf> {
+    self.resolved.get(from)?.get(spec.to_str()?)
+  }
+
   /// Compute reachable files from entrypoints
   pub fn compute_reachable(&self, entrypoints: Vec<PathBuf>) -> HashSet<PathBuf> {
     let file_set: HashSet<PathBuf> = self.files.keys().cloned().collect();
@@ -161,1304 +543,6226 @@ impl ProjectAnalyzer {
     visited
   }
 
-  pub fn find_unused_exports(&self) -> Vec<(PathBuf, String)> {
-    let file_set: HashSet<PathBuf> = self.files.keys().cloned().collect();
-    let mut unused_set: HashSet<(PathBuf, String)> = HashSet::new();
+  /// Import paths from the entrypoints to `target`, shortest first and at most `limit` of
+  /// them. Each lists the files from an entrypoint down to `target` without repeating one,
+  /// so an entrypoint that is `target` is a path of its own. Paths are found one at a time
+  /// with Yen's algorithm, so the work grows with `limit` rather than with every path.
+  pub fn find_import_paths(
+    &self,
+    entrypoints: Vec<PathBuf>,
+    target: &Path,
+    limit: usize,
+  ) -> Vec<Vec<PathBuf>> {
+    let target = normalize_soft(target);
+    let reachable = self.compute_reachable(entrypoints.clone());
+    if limit == 0 || !reachable.contains(&target) {
+      return Vec::new();
+    }
 
-    for (module_path, pf) in &self.files {
-      for export in &pf.exports {
-        if let ExportItem::Named(exp) = export {
-          let mut used = false;
-
-          let mut reexporters: Vec<PathBuf> = Vec::new();
-
-          for (other_module_path, other_pf) in &self.files {
-            if other_module_path != module_path {
-              for other_export in &other_pf.exports {
-                let has_named_reexport = if let ExportItem::Named(other_exp) = other_export {
-                  if let Some(src) = &other_exp.source {
-                    if let Some(spec) = src.to_str()
-                      && let Some(target) = resolve_relative_import_from_set(
-                        other_module_path,
-                        spec,
-                        &file_set,
-                        &self.config.alias,
-                      )
-                      && &target == module_path
-                      && other_exp.name == exp.name
-                    {
-                      true
-                    } else {
-                      false
-                    }
-                  } else {
-                    false
-                  }
-                } else {
-                  false
-                };
-
-                let has_all_reexport = if let ExportItem::All(specifier_path) = other_export
-                  && let Some(spec) = specifier_path.to_str()
-                  && let Some(target) = resolve_relative_import_from_set(
-                    other_module_path,
-                    spec,
-                    &file_set,
-                    &self.config.alias,
-                  )
-                  && &target == module_path
-                {
-                  true
-                } else {
-                  false
-                };
-
-                if has_named_reexport || has_all_reexport {
-                  reexporters.push(other_module_path.clone());
-                }
-              }
-            }
-          }
+    // Only extend paths through files that can still lead to `target`
+    let leads: HashSet<PathBuf> = self
+      .dependents(std::slice::from_ref(&target))
+      .into_iter()
+      .filter(|file| reachable.contains(file))
+      .collect();
 
-          for reexporter_path in reexporters {
-            if let Some(importers) = self.import_usage.get(&reexporter_path) {
-              for (_importer_path, import_info) in importers {
-                if import_info.has_namespace
-                  || (exp.name == "default" && import_info.has_default)
-                  || import_info.specifiers.iter().any(|s| s == &exp.name)
-                {
-                  used = true;
-                  break;
-                }
-              }
-            }
-            if used {
-              break;
-            }
-          }
+    let mut entries: Vec<PathBuf> = entrypoints
+      .iter()
+      .map(|ep| normalize_soft(ep))
+      .filter(|ep| leads.contains(ep))
+      .collect();
+    entries.sort();
+    entries.dedup();
 
-          if let Some(importers) = self.import_usage.get(module_path) {
-            for (_importer_path, import_info) in importers {
-              if import_info.has_namespace
-                || (exp.name == "default" && import_info.has_default)
-                || import_info.specifiers.iter().any(|s| s == &exp.name)
-              {
-                used = true;
-                break;
-              }
-            }
+    // Shortest path from `sources`, or the entrypoint itself when it is `target`
+    let shortest = |sources: &[PathBuf], allowed: &dyn Fn(&PathBuf, &PathBuf) -> bool| {
+      if sources.contains(&target) {
+        return Some(vec![target.clone()]);
+      }
+      self.shortest_path(sources, &target, |from, to, _| {
+        leads.contains(to) && allowed(from, to)
+      })
+    };
+
+    let Some(first) = shortest(&entries, &|_, _| true) else {
+      return Vec::new();
+    };
+    let mut paths = vec![first];
+    // Deviations from the paths found so far, by length and then by files
+    let mut candidates: BTreeSet<(usize, Vec<PathBuf>)> = BTreeSet::new();
+
+    while paths.len() < limit {
+      let last = &paths[paths.len() - 1];
+
+      // Leave `last` after each of its prefixes, the empty one meaning another entrypoint
+      for i in 0..last.len() {
+        let root = &last[..i];
+        // Files the paths sharing `root` go to next
+        let taken: HashSet<&PathBuf> = paths
+          .iter()
+          .filter(|path| path.len() > i && path[..i] == *root)
+          .map(|path| &path[i])
+          .collect();
+
+        let candidate = match root.split_last() {
+          None => {
+            let sources: Vec<PathBuf> = entries
+              .iter()
+              .filter(|ep| !taken.contains(ep))
+              .cloned()
+              .collect();
+            shortest(&sources, &|_, _| true)
           }
+          Some((spur, before)) => shortest(std::slice::from_ref(spur), &|from, to| {
+            !before.contains(to) && (from != spur || !taken.contains(to))
+          })
+          .map(|rest| [before, &rest].concat()),
+        };
 
-          if !used {
-            unused_set.insert((module_path.clone(), exp.name.clone()));
-          }
+        if let Some(candidate) = candidate
+          && !paths.contains(&candidate)
+        {
+          candidates.insert((candidate.len(), candidate));
         }
       }
+
+      let Some((_, next)) = candidates.pop_first() else {
+        break;
+      };
+      paths.push(next);
     }
 
-    let mut unused_vec: Vec<_> = unused_set.into_iter().collect();
-    unused_vec.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
-    unused_vec
+    paths
   }
-}
 
-/// Parse a source string in memory
-fn parse_module_from_path(path: &Path, source: &str) -> Result<ParsedFile> {
-  let allocator = Allocator::new();
+  /// The import keeping the edge from `from` to `to` alive: a runtime one when there is any
+  pub fn edge(&self, from: &Path, to: &Path) -> Option<&ImportEdge> {
+    Some(primary_import(self.edges.get(from)?.get(to)?))
+  }
 
-  // Check if this is a Vue file
-  let (program, errors) = if path.extension().and_then(|s| s.to_str()) == Some("vue") {
-    let vue_parser = VueOxcParser::new(&allocator, source);
-    let parsed = vue_parser.parse();
-    (parsed.program, parsed.errors)
-  } else {
-    // Standard JS/TS parsing
-    let source_type = SourceType::from_path(path).unwrap_or(SourceType::ts());
-    let parser = OxcParser::new(&allocator, source, source_type);
-    let parsed = parser.parse();
-    (parsed.program, parsed.errors)
-  };
+  /// `files` and every file importing one of them, directly or not
+  pub fn dependents(&self, files: &[PathBuf]) -> HashSet<PathBuf> {
+    let mut importers: HashMap<&PathBuf, Vec<&PathBuf>> = HashMap::new();
+    for (from, targets) in &self.graph {
+      for to in targets {
+        importers.entry(to).or_default().push(from);
+      }
+    }
 
-  if !errors.is_empty() {
-    eprintln!(
-      "Parser errors in {}: {} error(s)",
-      path.display(),
-      errors.len()
-    );
-  }
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    let mut stack: Vec<PathBuf> = files.iter().map(|f| normalize_soft(f)).collect();
+    while let Some(file) = stack.pop() {
+      if !visited.insert(file.clone()) {
+        continue;
+      }
+      for importer in importers.get(&file).into_iter().flatten() {
+        stack.push((*importer).clone());
+      }
+    }
 
-  let pf = extract_imports_exports(&program);
-  Ok(pf)
-}
+    visited
+  }
 
-fn normalize_soft(path: &Path) -> PathBuf {
-  PathBuf::from(path).clean()
-}
+  /// Source files affected by a change to `changed`, i.e. importing one of them directly or
+  /// not, the changed files themselves included, along with the affected entrypoints. Both
+  /// sorted. Type-only imports count, since a changed type can still break its importers.
+  /// Changed paths without a graph edge, like deleted files and assets, affect the files
+  /// whose specifiers or globs would match them.
+  pub fn find_affected(
+    &self,
+    entrypoints: &[PathBuf],
+    changed: &[PathBuf],
+  ) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut seeds: Vec<PathBuf> = changed.iter().map(|f| normalize_soft(f)).collect();
+    // Project files already have their edges, and matching them here would ignore which
+    // file a specifier really resolves to
+    let changed_set: HashSet<PathBuf> = seeds
+      .iter()
+      .filter(|file| !self.files.contains_key(*file))
+      .cloned()
+      .collect();
 
-/// Extract statements from Vue SFC script blocks
-/// Vue files are parsed as JSX fragments containing <script> elements with the actual code
-fn extract_vue_script_statements<'a>(program: &'a Program<'a>) -> Vec<&'a Statement<'a>> {
-  let mut statements = Vec::new();
+    for (path, pf) in &self.files {
+      let specifiers = pf
+        .imports
+        .iter()
+        .map(|import| import.source.as_str())
+        .chain(pf.exports.iter().filter_map(|e| e.source()?.to_str()));
+      let imports_changed = specifiers.into_iter().any(|spec| {
+        let bare = spec.split_once('?').map_or(spec, |(bare, _)| bare);
+        (is_relative(bare) || is_aliased(bare, &self.config))
+          && resolve_relative_import_from_set(path, bare, &changed_set, &self.config).is_some()
+      });
+      let globs_changed = pf
+        .globs
+        .iter()
+        .any(|glob| !expand_glob(path, glob, &changed_set, &self.config).is_empty());
 
-  for stmt in &program.body {
-    if let Statement::ExpressionStatement(expr_stmt) = stmt
-      && let Expression::JSXFragment(fragment) = &expr_stmt.expression
-    {
-      // Look for <script> elements in the fragment
-      for child in &fragment.children {
-        if let oxc_ast::ast::JSXChild::Element(element) = child
-          && let oxc_ast::ast::JSXElementName::Identifier(id) = &element.opening_element.name
-          && id.name == "script"
-        {
-          // Extract statements from the script content
-          for script_child in &element.children {
-            if let oxc_ast::ast::JSXChild::ExpressionContainer(container) = script_child
-              && let oxc_ast::ast::JSXExpression::ArrowFunctionExpression(arrow) =
-                &container.expression
-            {
-              for body_stmt in &arrow.body.statements {
-                statements.push(body_stmt);
-              }
-            }
-          }
-        }
+      if imports_changed || globs_changed {
+        seeds.push(path.clone());
       }
     }
-  }
 
-  statements
-}
+    let affected = self.dependents(&seeds);
 
-fn extract_imports_exports(program: &Program) -> ParsedFile {
-  let mut imports: Vec<ImportInfo> = Vec::new();
-  let mut exports: Vec<ExportItem> = Vec::new();
+    let mut files: Vec<PathBuf> = affected
+      .iter()
+      .filter(|file| self.files.contains_key(*file))
+      .cloned()
+      .collect();
+    files.sort();
 
-  // Try to extract Vue script statements first
-  let vue_statements = extract_vue_script_statements(program);
-  let statements_to_process: Vec<&Statement> = if !vue_statements.is_empty() {
-    vue_statements
-  } else {
-    // Regular JS/TS file, use program.body directly
-    program.body.iter().collect()
-  };
+    let mut entries: Vec<PathBuf> = entrypoints
+      .iter()
+      .map(|ep| normalize_soft(ep))
+      .filter(|ep| affected.contains(ep))
+      .collect();
+    entries.sort();
+    entries.dedup();
 
-  for stmt in statements_to_process {
-    match stmt {
-      Statement::ImportDeclaration(import) => {
-        let source_s = import.source.value.to_string();
-        let mut specifiers = Vec::new();
-        let mut has_namespace = false;
-        let mut has_default = false;
+    (files, entries)
+  }
 
-        if let Some(_specifiers) = &import.specifiers {
-          for spec in _specifiers {
-            match spec {
-              ImportDeclarationSpecifier::ImportSpecifier(named) => {
-                let name = named.imported.name().to_string();
-                specifiers.push(name);
-              }
-              ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => {
-                has_namespace = true;
-              }
-              ImportDeclarationSpecifier::ImportDefaultSpecifier(_) => {
-                has_default = true;
-              }
-            }
-          }
-        }
+  /// Source files not reachable from the entrypoints, minus the ignored kinds
+  pub fn find_unused_files(&self, reachable: &HashSet<PathBuf>) -> Vec<PathBuf> {
+    let options = &self.config.unused_files;
+
+    let mut unused: Vec<PathBuf> = self
+      .files
+      .keys()
+      .filter(|p| !reachable.contains(*p))
+      .filter(|p| !(options.ignore_config_files && is_config_file(p)))
+      .filter(|p| !(options.ignore_declaration_files && is_declaration_file(p)))
+      .filter(|p| !(options.ignore_test_files && is_test_file(p)))
+      .cloned()
+      .collect();
 
-        imports.push(ImportInfo {
-          source: source_s,
-          specifiers,
-          has_namespace,
-          has_default,
-        });
-      }
+    unused.sort();
+    unused
+  }
 
-      Statement::ExportNamedDeclaration(export) => {
-        if let Some(src) = &export.source {
-          for spec in &export.specifiers {
-            let exported = spec
-              .exported
-              .identifier_name()
-              .map(|n| n.to_string())
-              .or_else(|| spec.local.identifier_name().map(|n| n.to_string()))
-              .unwrap_or_else(|| "<unknown>".to_string());
+  /// Dependencies of the root package.json that no `reachable` file imports. An `@types`
+  /// package is used along with the package it types; one typing an unlisted package
+  /// (`@types/node`, `@types/jest`) may only provide globals, so it is never reported.
+  pub fn find_unused_dependencies(
+    &self,
+    reachable: &HashSet<PathBuf>,
+  ) -> Vec<(String, DependencyKind)> {
+    let Some(root) = self.config.packages.get(Path::new("")) else {
+      return Vec::new();
+    };
+
+    let used: HashSet<&str> = self
+      .external
+      .iter()
+      .filter(|(file, _)| reachable.contains(*file))
+      .flat_map(|(_, names)| names.keys().map(|n| n.as_str()))
+      .collect();
+    let declared: HashSet<&str> = root.all_dependencies().map(|(name, _)| name).collect();
+
+    let mut unused: Vec<(String, DependencyKind)> = root
+      .all_dependencies()
+      .filter(|(name, _)| match types_package_target(name) {
+        Some(typed) => declared.contains(typed.as_str()) && !used.contains(typed.as_str()),
+        None => !used.contains(name),
+      })
+      .map(|(name, kind)| (name.to_string(), kind))
+      .collect();
 
-            let source_path = PathBuf::from(src.value.to_string());
+    unused.sort_by(|a, b| a.0.cmp(&b.0));
+    unused
+  }
 
-            exports.push(ExportItem::Named(ExportInfo {
-              name: exported,
-              source: Some(source_path),
-            }));
-          }
-        } else {
-          if let Some(decl) = &export.declaration {
-            match decl {
-              Declaration::TSInterfaceDeclaration(int) => {
-                exports.push(ExportItem::Named(ExportInfo {
-                  name: int.id.name.to_string(),
-                  source: None,
-                }));
-              }
-              Declaration::TSTypeAliasDeclaration(ta) => {
-                exports.push(ExportItem::Named(ExportInfo {
-                  name: ta.id.name.to_string(),
-                  source: None,
-                }));
-              }
-              Declaration::TSEnumDeclaration(en) => {
-                exports.push(ExportItem::Named(ExportInfo {
-                  name: en.id.name.to_string(),
-                  source: None,
-                }));
-              }
-              Declaration::TSModuleDeclaration(md) => {
-                exports.push(ExportItem::Named(ExportInfo {
-                  name: md.id.name().to_string(),
-                  source: None,
-                }));
-              }
-              Declaration::FunctionDeclaration(fd) => {
-                if let Some(id) = &fd.id {
-                  exports.push(ExportItem::Named(ExportInfo {
-                    name: id.name.to_string(),
-                    source: None,
-                  }));
-                }
-              }
-              Declaration::VariableDeclaration(vd) => {
-                for declarator in &vd.declarations {
-                  let exported = declarator
-                    .id
-                    .get_identifier_name()
-                    .map(|n| n.to_string())
-                    .unwrap_or_else(|| "<unknown>".to_string());
-                  exports.push(ExportItem::Named(ExportInfo {
-                    name: exported,
-                    source: None,
-                  }));
-                }
-              }
-              Declaration::ClassDeclaration(cd) => {
-                if let Some(id) = &cd.id {
-                  exports.push(ExportItem::Named(ExportInfo {
-                    name: id.name.to_string(),
-                    source: None,
-                  }));
-                }
-              }
-              _ => {}
-            }
-          }
+  /// Packages `reachable` files import that their nearest package.json does not declare,
+  /// with that package.json's directory and the importing files. Listing only the
+  /// `@types` package is enough for type imports, and a package may import itself.
+  pub fn find_unlisted_dependencies(
+    &self,
+    reachable: &HashSet<PathBuf>,
+  ) -> Vec<(String, PathBuf, Vec<PathBuf>)> {
+    self.find_dependency_imports(reachable, |package, name, only_types| {
+      package.name.as_deref() != Some(name)
+        && !package.all_dependencies().any(|(dep, _)| {
+          dep == name || (only_types && types_package_target(dep).as_deref() == Some(name))
+        })
+    })
+  }
 
-          for spec in &export.specifiers {
-            let exported = spec
-              .exported
-              .identifier_name()
-              .map(|n| n.to_string())
-              .or_else(|| spec.local.identifier_name().map(|n| n.to_string()))
-              .unwrap_or_else(|| "<unknown>".to_string());
+  /// Packages only listed in `devDependencies` of the nearest package.json, but imported
+  /// at runtime by reachable production files: not tests, config or declaration files
+  pub fn find_misplaced_dependencies(
+    &self,
+    reachable: &HashSet<PathBuf>,
+  ) -> Vec<(String, PathBuf, Vec<PathBuf>)> {
+    let production: HashSet<PathBuf> = reachable
+      .iter()
+      .filter(|p| !is_test_file(p) && !is_config_file(p) && !is_declaration_file(p))
+      .cloned()
+      .collect();
 
-            exports.push(ExportItem::Named(ExportInfo {
-              name: exported,
-              source: None,
-            }));
+    self.find_dependency_imports(&production, |package, name, only_types| {
+      let listed = |deps: &[String]| deps.iter().any(|dep| dep == name);
+      !only_types
+        && listed(&package.dev_dependencies)
+        && !listed(&package.dependencies)
+        && !listed(&package.peer_dependencies)
+    })
+  }
+
+  /// Package imports of `files` the `matches` predicate flags against the nearest
+  /// package.json, grouped by package name and package.json directory
+  fn find_dependency_imports(
+    &self,
+    files: &HashSet<PathBuf>,
+    matches: impl Fn(&PackageJson, &str, bool) -> bool,
+  ) -> Vec<(String, PathBuf, Vec<PathBuf>)> {
+    let mut found: HashMap<(String, PathBuf), Vec<PathBuf>> = HashMap::new();
+
+    for (file, packages) in &self.external {
+      if !files.contains(file) {
+        continue;
+      }
+      let Some((dir, package)) = self.nearest_package(file) else {
+        continue;
+      };
+
+      for (name, only_types) in packages {
+        if matches(package, name, *only_types) {
+          found
+            .entry((name.clone(), dir.clone()))
+            .or_default()
+            .push(file.clone());
+        }
+      }
+    }
+
+    let mut found: Vec<(String, PathBuf, Vec<PathBuf>)> = found
+      .into_iter()
+      .map(|((name, dir), mut importers)| {
+        importers.sort();
+        (name, dir, importers)
+      })
+      .collect();
+    found.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+    found
+  }
+
+  /// The package.json closest to `file`, with its directory
+  fn nearest_package(&self, file: &Path) -> Option<(PathBuf, &PackageJson)> {
+    file
+      .ancestors()
+      .skip(1)
+      .find_map(|dir| Some((dir.to_path_buf(), self.config.packages.get(dir)?)))
+  }
+
+  /// Relative and aliased specifiers that match no project file, with the reason and the
+  /// 1-based line and column of the specifier. Query strings (`./worker?worker`) are
+  /// ignored, and so are asset imports such as `./logo.svg`.
+  pub fn find_unresolved_imports(&self) -> Vec<(PathBuf, String, UnresolvedReason, (u32, u32))> {
+    let file_set: HashSet<PathBuf> = self.files.keys().cloned().collect();
+    let lowercase_set: HashSet<PathBuf> = file_set.iter().map(|p| lowercase_path(p)).collect();
+    let mut found = Vec::new();
+
+    for (path, pf) in &self.files {
+      let mut seen = HashSet::new();
+      let specs = pf.imports.iter().map(|imp| imp.source.as_str()).chain(
+        pf.exports
+          .iter()
+          .filter_map(|export| export.source()?.to_str()),
+      );
+
+      for spec in specs {
+        if !seen.insert(spec)
+          || self.resolve(path, Path::new(spec)).is_some()
+          || !(is_relative(spec) || is_aliased(spec, &self.config))
+        {
+          continue;
+        }
+
+        let bare = spec.split_once('?').map_or(spec, |(bare, _)| bare);
+        let is_asset = Path::new(bare)
+          .extension()
+          .and_then(|ext| ext.to_str())
+          .is_some_and(|ext| ASSET_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+        if is_asset
+          || resolve_relative_import_from_set(path, bare, &file_set, &self.config).is_some()
+        {
+          continue;
+        }
+
+        let reason = unresolved_reason(path, bare, &file_set, &lowercase_set, &self.config);
+        let position = pf
+          .specifier_positions
+          .get(spec)
+          .copied()
+          .unwrap_or_default();
+        found.push((path.clone(), spec.to_string(), reason, position));
+      }
+    }
+
+    found.sort_by(|a, b| a.0.cmp(&b.0).then(a.3.cmp(&b.3)).then(a.1.cmp(&b.1)));
+    found
+  }
+
+  /// Every module consuming export `name` of `module`, following re-exports through
+  /// barrels (named, `export *` and `export * as ns`) and namespace imports. Barrels are
+  /// listed too, as re-export references.
+  pub fn find_references(&self, module: &Path, name: &str) -> Vec<Reference> {
+    // target module -> re-exports forwarding from it
+    let mut reexports: HashMap<&PathBuf, Vec<(&PathBuf, &ExportItem)>> = HashMap::new();
+    for (file, pf) in &self.files {
+      for export in &pf.exports {
+        if let Some(target) = export.source().and_then(|src| self.resolve(file, src)) {
+          reexports.entry(target).or_default().push((file, export));
+        }
+      }
+    }
+
+    let position = |file: &Path, spec: &Path| -> (u32, u32) {
+      spec
+        .to_str()
+        .and_then(|spec| self.files.get(file)?.specifier_positions.get(spec))
+        .copied()
+        .unwrap_or_default()
+    };
+
+    let start = (normalize_soft(module), name.to_string());
+    let mut visited = HashSet::from([start.clone()]);
+    let mut stack = vec![start];
+    let mut references = Vec::new();
+
+    while let Some((target, target_name)) = stack.pop() {
+      for (file, export) in reexports.get(&target).into_iter().flatten() {
+        let (alias, exp_position, type_only) = match export {
+          ExportItem::Named(exp) if exp.local == target_name => {
+            (exp.name.clone(), (exp.line, exp.column), exp.type_only)
           }
+          ExportItem::Namespace(exp) => (exp.name.clone(), (exp.line, exp.column), exp.type_only),
+          // `export *` never forwards `default`, and own exports shadow it
+          ExportItem::All(src)
+            if target_name != "default"
+              && self.find_export(file, &target_name).map(|(m, _)| m) != Some((*file).clone()) =>
+          {
+            (target_name.clone(), position(file, src), false)
+          }
+          _ => continue,
+        };
+
+        references.push(Reference {
+          file: (*file).clone(),
+          line: exp_position.0,
+          column: exp_position.1,
+          kind: ReferenceKind::ReExport,
+          specifier: export
+            .source()
+            .map_or_else(String::new, |s| s.to_string_lossy().to_string()),
+          name: target_name.clone(),
+          type_only,
+        });
+        if visited.insert(((*file).clone(), alias.clone())) {
+          stack.push(((*file).clone(), alias));
         }
       }
 
-      Statement::ExportDefaultDeclaration(_) => {
-        exports.push(ExportItem::Named(ExportInfo {
-          name: "default".to_string(),
-          source: None,
-        }));
+      let module = self.files.get(&target);
+      for (importer, info) in self.import_usage.get(&target).into_iter().flatten() {
+        let kind = if info.specifiers.contains(&target_name) {
+          Some(ReferenceKind::Named)
+        } else if info.has_default && target_name == "default" {
+          Some(ReferenceKind::Default)
+        } else if info.has_default && module.is_some_and(|pf| pf.is_commonjs) {
+          Some(ReferenceKind::Namespace)
+        } else if info.has_namespace {
+          match &info.namespace_members {
+            None => Some(ReferenceKind::Namespace),
+            Some(members) if members.contains(&target_name) => Some(ReferenceKind::NamespaceMember),
+            Some(_) => None,
+          }
+        } else {
+          None
+        };
+        let Some(kind) = kind else {
+          continue;
+        };
+
+        let (line, column) = position(importer, Path::new(&info.source));
+        references.push(Reference {
+          file: importer.clone(),
+          line,
+          column,
+          kind,
+          specifier: info.source.clone(),
+          name: target_name.clone(),
+          type_only: info.is_type_use(&target_name),
+        });
       }
+    }
 
-      Statement::ExportAllDeclaration(export_all) => {
-        exports.push(ExportItem::All(PathBuf::from(
-          export_all.source.value.to_string(),
-        )));
+    references.sort();
+    references.dedup();
+    references
+  }
+
+  /// Import cycles, one per strongly connected component. Runtime cycles come from the
+  /// edges that exist at runtime. Components that only form once type-only imports are
+  /// added are type cycles, their path going through at least one type-only edge.
+  pub fn find_cycles(&self) -> Vec<ImportCycle> {
+    let mut cycles = Vec::new();
+
+    let runtime = self.strongly_connected_components(true);
+    let runtime_sets: HashSet<Vec<PathBuf>> = runtime.iter().cloned().collect();
+    for files in runtime {
+      let members: HashSet<&PathBuf> = files.iter().collect();
+      let start = &files[0];
+      let Some(mut nodes) =
+        self.shortest_path(std::slice::from_ref(start), start, |_, to, edge| {
+          !edge.type_only && members.contains(to)
+        })
+      else {
+        continue;
+      };
+      nodes.pop();
+      cycles.push(self.import_cycle(false, files.clone(), nodes));
+    }
+
+    for files in self.strongly_connected_components(false) {
+      if runtime_sets.contains(&files) {
+        continue;
       }
+      let members: HashSet<&PathBuf> = files.iter().collect();
+      // Close the loop through the first type-only edge of the component
+      let type_edge = files.iter().find_map(|from| {
+        let mut targets: Vec<&PathBuf> = self
+          .edges
+          .get(from)?
+          .iter()
+          .filter(|(to, imports)| primary_import(imports).type_only && members.contains(to))
+          .map(|(to, _)| to)
+          .collect();
+        targets.sort();
+        Some((from, *targets.first()?))
+      });
+      let Some((from, to)) = type_edge else {
+        continue;
+      };
+      let Some(path) = self.shortest_path(std::slice::from_ref(to), from, |_, to, _| {
+        members.contains(to)
+      }) else {
+        continue;
+      };
+
+      let mut nodes = vec![from.clone()];
+      nodes.extend(path.into_iter().take_while(|node| node != from));
+      cycles.push(self.import_cycle(true, files.clone(), nodes));
+    }
 
-      _ => {}
+    cycles.sort_by(|a, b| a.type_only.cmp(&b.type_only).then(a.files.cmp(&b.files)));
+    cycles
+  }
+
+  fn import_cycle(&self, type_only: bool, files: Vec<PathBuf>, nodes: Vec<PathBuf>) -> ImportCycle {
+    let path = nodes
+      .iter()
+      .enumerate()
+      .map(|(i, node)| {
+        let next = &nodes[(i + 1) % nodes.len()];
+        (
+          node.clone(),
+          primary_import(&self.edges[node][next]).clone(),
+        )
+      })
+      .collect();
+
+    ImportCycle {
+      type_only,
+      files,
+      path,
     }
   }
 
-  ParsedFile { imports, exports }
-}
+  /// Sorted strongly connected components with a cycle: several files, or a file importing
+  /// itself. Iterative Tarjan, so long import chains cannot overflow the stack.
+  fn strongly_connected_components(&self, runtime_only: bool) -> Vec<Vec<PathBuf>> {
+    let mut nodes: Vec<&PathBuf> = self.files.keys().collect();
+    nodes.sort();
+    let ids: HashMap<&PathBuf, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+    let successors: Vec<Vec<usize>> = nodes
+      .iter()
+      .map(|node| {
+        let mut next: Vec<usize> = self
+          .edges
+          .get(*node)
+          .into_iter()
+          .flatten()
+          .filter(|(_, imports)| !(runtime_only && primary_import(imports).type_only))
+          .filter_map(|(to, _)| ids.get(to).copied())
+          .collect();
+        next.sort();
+        next
+      })
+      .collect();
 
-/// Check if a module specifier is relative
-fn is_relative(spec: &str) -> bool {
-  spec.starts_with('.')
-}
+    let mut index = vec![usize::MAX; nodes.len()];
+    let mut low = vec![0; nodes.len()];
+    let mut on_stack = vec![false; nodes.len()];
+    let mut stack = Vec::new();
+    let mut next_index = 0;
+    let mut components = Vec::new();
 
-/// Resolve relative import in memory using a HashSet of file paths
-pub fn resolve_relative_import_from_set(
-  from: &Path,
-  spec: &str,
-  file_set: &HashSet<PathBuf>,
-  aliases: &HashMap<String, String>,
-) -> Option<PathBuf> {
-  let candidate = normalize_soft(Path::new(spec));
+    for root in 0..nodes.len() {
+      if index[root] != usize::MAX {
+        continue;
+      }
+      index[root] = next_index;
+      low[root] = next_index;
+      next_index += 1;
+      stack.push(root);
+      on_stack[root] = true;
+      let mut calls = vec![(root, 0)];
+
+      while let Some(&(v, i)) = calls.last() {
+        if let Some(&w) = successors[v].get(i) {
+          calls.last_mut().unwrap().1 += 1;
+          if index[w] == usize::MAX {
+            index[w] = next_index;
+            low[w] = next_index;
+            next_index += 1;
+            stack.push(w);
+            on_stack[w] = true;
+            calls.push((w, 0));
+          } else if on_stack[w] {
+            low[v] = low[v].min(index[w]);
+          }
+          continue;
+        }
 
-  let candidate = if is_relative(spec) {
-    let from_dir = from.parent().unwrap_or(Path::new(""));
-    normalize_soft(&from_dir.join(&candidate))
-  } else {
-    // Check if spec starts with an alias
-    let mut resolved_candidate = None;
-    for (alias, target) in aliases {
-      if spec.starts_with(alias) {
-        let remainder = &spec[alias.len()..];
-        let remainder = remainder.trim_start_matches('/');
-        resolved_candidate = Some(normalize_soft(&PathBuf::from(target).join(remainder)));
-        break;
+        calls.pop();
+        if let Some(&(parent, _)) = calls.last() {
+          low[parent] = low[parent].min(low[v]);
+        }
+        if low[v] == index[v] {
+          let mut component = Vec::new();
+          while let Some(w) = stack.pop() {
+            on_stack[w] = false;
+            component.push(nodes[w].clone());
+            if w == v {
+              break;
+            }
+          }
+          if component.len() > 1 || successors[v].contains(&v) {
+            component.sort();
+            components.push(component);
+          }
+        }
       }
     }
-    resolved_candidate.unwrap_or(candidate)
-  };
 
-  for ext in SUPPORTED_EXTENSIONS {
-    let with_extension = candidate.with_extension(ext.trim_start_matches('.'));
+    components
+  }
+
+  /// Shortest path of files from one of `from` to `to` along edges `allowed` accepts, both
+  /// ends included. Ties go to the earliest source, then to the files sorting first. When
+  /// `to` is a source, the path is a loop of at least one edge.
+  fn shortest_path(
+    &self,
+    from: &[PathBuf],
+    to: &Path,
+    allowed: impl Fn(&PathBuf, &PathBuf, &ImportEdge) -> bool,
+  ) -> Option<Vec<PathBuf>> {
+    let sources: HashSet<&PathBuf> = from.iter().collect();
+    let mut parents: HashMap<PathBuf, PathBuf> = HashMap::new();
+    let mut queue: VecDeque<PathBuf> = from.iter().cloned().collect();
+
+    while let Some(node) = queue.pop_front() {
+      let mut next: Vec<(&PathBuf, &ImportEdge)> = self
+        .edges
+        .get(&node)
+        .into_iter()
+        .flatten()
+        .map(|(target, imports)| (target, primary_import(imports)))
+        .filter(|(target, edge)| allowed(&node, target, edge))
+        .collect();
+      next.sort_by(|a, b| a.0.cmp(b.0));
+
+      for (target, _) in next {
+        if target != to && (sources.contains(target) || parents.contains_key(target)) {
+          continue;
+        }
+        parents.insert(target.clone(), node.clone());
+        if target != to {
+          queue.push_back(target.clone());
+          continue;
+        }
 
-    if file_set.contains(&with_extension) {
-      return Some(with_extension);
+        let mut path = vec![target.clone()];
+        let mut current = target;
+        loop {
+          current = &parents[current];
+          path.push(current.clone());
+          if sources.contains(current) {
+            break;
+          }
+        }
+        path.reverse();
+        return Some(path);
+      }
     }
+
+    None
   }
 
-  if file_set.contains(&candidate) {
-    return Some(candidate);
+  pub fn find_unused_exports(&self) -> Vec<(PathBuf, ExportInfo)> {
+    self.find_unused_exports_with(None)
   }
 
-  None
-}
+  /// Find unused exports, counting only imports made by `live` files when given.
+  /// Passing the result of `compute_reachable` (the fixed point of the import graph from
+  /// the entrypoints) makes a dead subgraph and the exports only it uses unused together.
+  pub fn find_unused_exports_with(
+    &self,
+    live: Option<&HashSet<PathBuf>>,
+  ) -> Vec<(PathBuf, ExportInfo)> {
+    let usage = self.compute_export_usage(live, false);
+    let mut unused_vec: Vec<(PathBuf, ExportInfo)> = Vec::new();
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-  use std::collections::HashMap;
-  use std::path::PathBuf;
+    for (module_path, pf) in &self.files {
+      for export in &pf.exports {
+        if let ExportItem::Named(exp) | ExportItem::Namespace(exp) = export
+          && !usage.is_used(module_path, &exp.name)
+        {
+          unused_vec.push((module_path.clone(), exp.clone()));
+        }
+      }
+    }
 
-  /// Test project builder for creating in-memory test projects
-  struct TestProject {
-    sources: HashMap<PathBuf, String>,
-    entries: Vec<PathBuf>,
+    unused_vec.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.name.cmp(&b.1.name)));
+    unused_vec.dedup_by(|a, b| a.0 == b.0 && a.1.name == b.1.name);
+    unused_vec
   }
 
-  impl TestProject {
-    fn new() -> Self {
-      Self {
-        sources: HashMap::new(),
-        entries: Vec::new(),
+  /// Whether an export only carries a type: a type alias or interface, a type-only export,
+  /// or a re-export of one of those
+  pub fn is_type_export(&self, module: &Path, export: &ExportInfo) -> bool {
+    let mut module = module.to_path_buf();
+    let mut export = export;
+    let mut visited = HashSet::new();
+
+    loop {
+      if export.declares_type() {
+        return true;
+      }
+      if export.kind != ExportKind::ReExport || !visited.insert((module.clone(), &export.name)) {
+        return false;
+      }
+
+      let next = export
+        .source
+        .as_ref()
+        .and_then(|src| self.resolve(&module, src))
+        .and_then(|target| self.find_export(target, &export.local));
+      match next {
+        Some((target, exp)) => {
+          module = target;
+          export = exp;
+        }
+        None => return false,
       }
     }
+  }
 
-    fn add_file(mut self, path: &str, content: &str) -> Self {
-      self
-        .sources
-        .insert(PathBuf::from(path), content.to_string());
-      self
+  /// Declaration of `name` among the exports of `module`, looking through `export *`
+  fn find_export(&self, module: &Path, name: &str) -> Option<(PathBuf, &ExportInfo)> {
+    let mut stack = vec![module.to_path_buf()];
+    let mut visited = HashSet::new();
+
+    while let Some(module) = stack.pop() {
+      if !visited.insert(module.clone()) {
+        continue;
+      }
+      let Some(pf) = self.files.get(&module) else {
+        continue;
+      };
+
+      for export in &pf.exports {
+        if let ExportItem::Named(exp) | ExportItem::Namespace(exp) = export
+          && exp.name == name
+        {
+          return Some((module, exp));
+        }
+      }
+
+      // `export *` never forwards `default`
+      if name != "default" {
+        for export in &pf.exports {
+          if let ExportItem::All(src) = export
+            && let Some(target) = self.resolve(&module, src)
+          {
+            stack.push(target.clone());
+          }
+        }
+      }
     }
 
-    fn entry(mut self, path: &str) -> Self {
-      self.entries.push(PathBuf::from(path));
-      self
+    None
+  }
+
+  /// Public API surface of the `public_entries`: every name an entry exports, with the
+  /// module and export declaring it once re-exports are followed. Re-exports of files
+  /// outside the project stop at the entry.
+  pub fn find_public_api(&self) -> Vec<(PathBuf, String, PathBuf, ExportInfo)> {
+    let mut found = Vec::new();
+
+    for entry in &self.config.public_entries {
+      let entry = normalize_soft(entry);
+      for name in self.exported_names(&entry) {
+        let Some((mut module, mut export)) = self.find_export(&entry, &name) else {
+          continue;
+        };
+        let mut visited = HashSet::new();
+        while export.kind == ExportKind::ReExport && visited.insert(module.clone()) {
+          let next = export
+            .source
+            .as_ref()
+            .and_then(|src| self.resolve(&module, src))
+            .and_then(|target| self.find_export(target, &export.local));
+          match next {
+            Some((target, exp)) => {
+              module = target;
+              export = exp;
+            }
+            None => break,
+          }
+        }
+        found.push((entry.clone(), name, module, export.clone()));
+      }
     }
 
-    fn build(self) -> (ProjectAnalyzer, Vec<PathBuf>) {
-      self.build_with_config(None)
+    found.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+    found.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1);
+    found
+  }
+
+  /// Every name `module` exports, including the ones forwarded by `export *`
+  fn exported_names(&self, module: &Path) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut stack = vec![module.to_path_buf()];
+    let mut visited = HashSet::new();
+
+    while let Some(current) = stack.pop() {
+      if !visited.insert(current.clone()) {
+        continue;
+      }
+      let Some(pf) = self.files.get(&current) else {
+        continue;
+      };
+
+      for export in &pf.exports {
+        match export {
+          ExportItem::Named(exp) | ExportItem::Namespace(exp) => {
+            // `export *` never forwards `default`
+            if current == module || exp.name != "default" {
+              names.push(exp.name.clone());
+            }
+          }
+          ExportItem::All(src) => {
+            if let Some(target) = self.resolve(&current, src) {
+              stack.push(target.clone());
+            }
+          }
+        }
+      }
     }
 
-    fn build_with_config(self, config: Option<SweepyConfig>) -> (ProjectAnalyzer, Vec<PathBuf>) {
-      let sources_ref: HashMap<PathBuf, &str> = self
-        .sources
-        .iter()
-        .map(|(p, c)| (PathBuf::from(p), c.as_str()))
-        .collect();
+    names.sort();
+    names.dedup();
+    names
+  }
 
-      let analyzer =
-        ProjectAnalyzer::from_sources(&sources_ref, config).expect("Failed to analyze");
-      (analyzer, self.entries)
+  /// Value exports that are used, but only by the type checker: every import of them is
+  /// type-only or only referenced in type positions. Each comes with its direct importers
+  /// that import it as a value and could switch to `import type`.
+  pub fn find_type_only_used_exports(
+    &self,
+    live: Option<&HashSet<PathBuf>>,
+  ) -> Vec<(PathBuf, ExportInfo, Vec<PathBuf>)> {
+    let usage = self.compute_export_usage(live, false);
+    let value_usage = self.compute_export_usage(live, true);
+    let mut found = Vec::new();
+
+    for (module_path, pf) in &self.files {
+      for export in &pf.exports {
+        let (ExportItem::Named(exp) | ExportItem::Namespace(exp)) = export else {
+          continue;
+        };
+        if !usage.is_used(module_path, &exp.name)
+          || value_usage.is_used(module_path, &exp.name)
+          || self.is_type_export(module_path, exp)
+        {
+          continue;
+        }
+
+        let mut importers: Vec<PathBuf> = self
+          .import_usage
+          .get(module_path)
+          .into_iter()
+          .flatten()
+          .filter(|(importer, _)| live.is_none_or(|l| l.contains(importer)))
+          .filter(|(_, info)| info.type_position_only.contains(&exp.name))
+          .map(|(importer, _)| importer.clone())
+          .collect();
+        importers.sort();
+        importers.dedup();
+
+        found.push((module_path.clone(), exp.clone(), importers));
+      }
     }
+
+    found.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.name.cmp(&b.1.name)));
+    found
   }
 
-  fn assert_reachable(analyzer: &ProjectAnalyzer, entries: &[PathBuf], expected: &[&str]) {
-    let reachable = analyzer.compute_reachable(entries.to_vec());
-    for path in expected {
-      assert!(
-        reachable.contains(&PathBuf::from(path)),
-        "Expected {} to be reachable",
-        path
-      );
+  /// Seed usage from every (live) import, then follow re-exports to the declaring modules.
+  /// With `values_only`, uses that only the type checker needs are left out.
+  fn compute_export_usage(
+    &self,
+    live: Option<&HashSet<PathBuf>>,
+    values_only: bool,
+  ) -> ExportUsage {
+    let mut usage = ExportUsage::default();
+
+    for entry in &self.config.public_entries {
+      let entry = normalize_soft(entry);
+      for name in self.exported_names(&entry) {
+        self.mark_export_used(&entry, &name, &mut usage);
+      }
+    }
+
+    for (target, importers) in &self.import_usage {
+      for (importer, import_info) in importers {
+        if live.is_some_and(|l| !l.contains(importer)) {
+          continue;
+        }
+        if values_only && import_info.type_only {
+          continue;
+        }
+
+        if import_info.has_namespace {
+          match &import_info.namespace_members {
+            Some(members) => {
+              for name in members {
+                self.mark_export_used(target, name, &mut usage);
+              }
+            }
+            None => self.mark_module_used(target, &mut usage),
+          }
+        }
+        if import_info.has_default && !(values_only && import_info.is_type_use("default")) {
+          self.mark_export_used(target, "default", &mut usage);
+        }
+        for name in &import_info.specifiers {
+          if !(values_only && import_info.is_type_use(name)) {
+            self.mark_export_used(target, name, &mut usage);
+          }
+        }
+      }
     }
+
+    usage
   }
 
-  fn assert_unused(analyzer: &ProjectAnalyzer, expected: Vec<(&str, &str)>) {
-    let mut unused = analyzer.find_unused_exports();
-    let mut expected_sorted: Vec<_> = expected
-      .into_iter()
-      .map(|(file, name)| (PathBuf::from(file), name.to_string()))
-      .collect();
-    expected_sorted.sort();
-    unused.sort();
-    assert_eq!(unused, expected_sorted);
+  /// Mark `name` of `module` as used, following named and `export *` re-exports.
+  /// Already visited pairs stop the walk, so `export *` cycles terminate.
+  fn mark_export_used(&self, module: &Path, name: &str, usage: &mut ExportUsage) {
+    let mut stack = vec![(module.to_path_buf(), name.to_string())];
+
+    while let Some((module, name)) = stack.pop() {
+      if !usage.used.insert((module.clone(), name.clone())) {
+        continue;
+      }
+      let Some(pf) = self.files.get(&module) else {
+        continue;
+      };
+
+      let mut found = false;
+      for export in &pf.exports {
+        match export {
+          ExportItem::Named(exp) if exp.name == name => {
+            found = true;
+            if let Some(src) = &exp.source
+              && let Some(target) = self.resolve(&module, src)
+            {
+              stack.push((target.clone(), exp.local.clone()));
+            }
+          }
+          ExportItem::Namespace(exp) if exp.name == name => {
+            found = true;
+            if let Some(src) = &exp.source
+              && let Some(target) = self.resolve(&module, src)
+            {
+              self.mark_module_used(target, usage);
+            }
+          }
+          _ => {}
+        }
+      }
+
+      // Interop default import of a CommonJS module reads the whole exports object
+      if !found && name == "default" && pf.is_commonjs {
+        self.mark_module_used(&module, usage);
+      }
+
+      // Own exports shadow `export *`, which never forwards `default`
+      if !found && name != "default" {
+        for export in &pf.exports {
+          if let ExportItem::All(src) = export
+            && let Some(target) = self.resolve(&module, src)
+          {
+            stack.push((target.clone(), name.clone()));
+          }
+        }
+      }
+    }
   }
 
-  // ===== Basic Named Exports =====
-  mod basic {
-    use super::*;
+  /// Mark every export of `module` as used, including everything it re-exports
+  fn mark_module_used(&self, module: &Path, usage: &mut ExportUsage) {
+    let mut stack = vec![module.to_path_buf()];
+
+    while let Some(module) = stack.pop() {
+      if !usage.fully_used.insert(module.clone()) {
+        continue;
+      }
+      let Some(pf) = self.files.get(&module) else {
+        continue;
+      };
+
+      for export in &pf.exports {
+        match export {
+          ExportItem::Named(exp) => {
+            if let Some(src) = &exp.source
+              && let Some(target) = self.resolve(&module, src)
+            {
+              self.mark_export_used(target, &exp.local, usage);
+            }
+          }
+          ExportItem::Namespace(exp) => {
+            if let Some(src) = &exp.source
+              && let Some(target) = self.resolve(&module, src)
+            {
+              stack.push(target.clone());
+            }
+          }
+          ExportItem::All(src) => {
+            if let Some(target) = self.resolve(&module, src) {
+              stack.push(target.clone());
+            }
+          }
+        }
+      }
+    }
+  }
+}
+
+/// Parse a source string in memory
+fn parse_module_from_path(path: &Path, source: &str) -> Result<ParsedFile> {
+  let allocator = Allocator::new();
+
+  // Check if this is a Vue file
+  let (program, errors) = if path.extension().and_then(|s| s.to_str()) == Some("vue") {
+    let vue_parser = VueOxcParser::new(&allocator, source);
+    let parsed = vue_parser.parse();
+    (parsed.program, parsed.errors)
+  } else {
+    // Standard JS/TS parsing
+    let source_type = SourceType::from_path(path).unwrap_or(SourceType::ts());
+    let parser = OxcParser::new(&allocator, source, source_type);
+    let parsed = parser.parse();
+    (parsed.program, parsed.errors)
+  };
+
+  if !errors.is_empty() {
+    eprintln!(
+      "Parser errors in {}: {} error(s)",
+      path.display(),
+      errors.len()
+    );
+  }
+
+  let pf = extract_imports_exports(&program);
+  Ok(pf)
+}
+
+/// File name split on dots: `vite.config.ts` -> ["vite", "config", "ts"]
+fn file_name_segments(path: &Path) -> Vec<&str> {
+  path
+    .file_name()
+    .and_then(|s| s.to_str())
+    .map(|name| name.split('.').collect())
+    .unwrap_or_default()
+}
+
+/// `*.d.ts`, `*.d.mts` and `*.d.cts` files
+pub fn is_declaration_file(path: &Path) -> bool {
+  let segments = file_name_segments(path);
+  segments.len() > 2 && segments[segments.len() - 2] == "d"
+}
+
+/// Tool configuration such as `vite.config.ts` or `.eslintrc.cjs`
+pub fn is_config_file(path: &Path) -> bool {
+  let segments = file_name_segments(path);
+  if segments.len() < 2 {
+    return false;
+  }
+
+  let is_dotted_config = segments.len() > 2 && segments[segments.len() - 2] == "config";
+  let is_rc_file = segments[0].is_empty() && segments[1].ends_with("rc");
+  is_dotted_config || is_rc_file
+}
+
+/// `*.test.*` / `*.spec.*` files and files under `__tests__` or `__mocks__`
+pub fn is_test_file(path: &Path) -> bool {
+  let segments = file_name_segments(path);
+  let has_test_segment = segments.len() > 2
+    && segments[1..segments.len() - 1]
+      .iter()
+      .any(|s| *s == "test" || *s == "spec");
+
+  has_test_segment
+    || path
+      .components()
+      .any(|c| c.as_os_str() == "__tests__" || c.as_os_str() == "__mocks__")
+}
+
+fn normalize_soft(path: &Path) -> PathBuf {
+  PathBuf::from(path).clean()
+}
+
+/// Extract statements from Vue SFC script blocks
+/// Vue files are parsed as JSX fragments containing <script> elements with the actual code
+fn extract_vue_script_statements<'a>(program: &'a Program<'a>) -> Vec<&'a Statement<'a>> {
+  vue_script_bodies(program)
+    .into_iter()
+    .flat_map(|arrow| arrow.body.statements.iter())
+    .collect()
+}
+
+/// The arrow functions the Vue parser wraps every `<script>` block's content in
+fn vue_script_bodies<'a>(program: &'a Program<'a>) -> Vec<&'a ArrowFunctionExpression<'a>> {
+  let mut bodies = Vec::new();
+
+  for stmt in &program.body {
+    if let Statement::ExpressionStatement(expr_stmt) = stmt
+      && let Expression::JSXFragment(fragment) = &expr_stmt.expression
+    {
+      // Look for <script> elements in the fragment
+      for child in &fragment.children {
+        if let oxc_ast::ast::JSXChild::Element(element) = child
+          && let oxc_ast::ast::JSXElementName::Identifier(id) = &element.opening_element.name
+          && id.name == "script"
+        {
+          for script_child in &element.children {
+            if let oxc_ast::ast::JSXChild::ExpressionContainer(container) = script_child
+              && let oxc_ast::ast::JSXExpression::ArrowFunctionExpression(arrow) =
+                &container.expression
+            {
+              bodies.push(&**arrow);
+            }
+          }
+        }
+      }
+    }
+  }
+
+  bodies
+}
+
+fn extract_imports_exports<'a>(program: &'a Program<'a>) -> ParsedFile {
+  let mut imports: Vec<ImportInfo> = Vec::new();
+  let mut exports: Vec<ExportItem> = Vec::new();
+  // local binding -> (index in `imports`, imported name)
+  let mut import_bindings: HashMap<String, (usize, String)> = HashMap::new();
+
+  // Try to extract Vue script statements first
+  let vue_statements = extract_vue_script_statements(program);
+  let statements_to_process: Vec<&Statement> = if !vue_statements.is_empty() {
+    vue_statements
+  } else {
+    // Regular JS/TS file, use program.body directly
+    program.body.iter().collect()
+  };
+
+  // Kind of every top-level declaration, for `export { a }`
+  let local_kinds: HashMap<String, ExportKind> = statements_to_process
+    .iter()
+    .filter_map(|stmt| match stmt {
+      Statement::ExportNamedDeclaration(export) => export.declaration.as_ref(),
+      _ => stmt.as_declaration(),
+    })
+    .flat_map(declaration_bindings)
+    .map(|(name, kind, _)| (name, kind))
+    .collect();
+
+  for stmt in statements_to_process {
+    match stmt {
+      Statement::ImportDeclaration(import) => {
+        let source_s = import.source.value.to_string();
+        let mut specifiers = Vec::new();
+        let mut type_specifiers = Vec::new();
+        let mut has_namespace = false;
+        let mut has_default = false;
+
+        if let Some(_specifiers) = &import.specifiers {
+          for spec in _specifiers {
+            match spec {
+              ImportDeclarationSpecifier::ImportSpecifier(named) => {
+                let name = named.imported.name().to_string();
+                import_bindings.insert(named.local.name.to_string(), (imports.len(), name.clone()));
+                if import.import_kind.is_type() || named.import_kind.is_type() {
+                  type_specifiers.push(name.clone());
+                }
+                specifiers.push(name);
+              }
+              ImportDeclarationSpecifier::ImportNamespaceSpecifier(ns) => {
+                import_bindings.insert(ns.local.name.to_string(), (imports.len(), "*".to_string()));
+                has_namespace = true;
+              }
+              ImportDeclarationSpecifier::ImportDefaultSpecifier(default) => {
+                import_bindings.insert(
+                  default.local.name.to_string(),
+                  (imports.len(), "default".to_string()),
+                );
+                has_default = true;
+              }
+            }
+          }
+        }
+
+        imports.push(ImportInfo {
+          source: source_s,
+          kind: ImportKind::Static,
+          specifiers,
+          has_namespace,
+          has_default,
+          namespace_members: None,
+          type_only: import.import_kind.is_type(),
+          type_specifiers,
+          type_position_only: Vec::new(),
+        });
+      }
+
+      Statement::ExportNamedDeclaration(export) => {
+        if let Some(src) = &export.source {
+          for spec in &export.specifiers {
+            let source_path = PathBuf::from(src.value.to_string());
+
+            exports.push(ExportItem::Named(ExportInfo {
+              name: spec.exported.name().to_string(),
+              local: spec.local.name().to_string(),
+              source: Some(source_path),
+              kind: ExportKind::ReExport,
+              type_only: export.export_kind.is_type() || spec.export_kind.is_type(),
+              span: spec.exported.span(),
+              ..Default::default()
+            }));
+          }
+        } else {
+          if let Some(decl) = &export.declaration {
+            // `export import A = require('./a')` also imports the module
+            if let Declaration::TSImportEqualsDeclaration(ie) = decl
+              && let TSModuleReference::ExternalModuleReference(reference) = &ie.module_reference
+            {
+              imports.push(ImportInfo {
+                source: reference.expression.value.to_string(),
+                kind: ImportKind::ImportEquals,
+                specifiers: Vec::new(),
+                has_namespace: true,
+                has_default: false,
+                namespace_members: None,
+                type_only: ie.import_kind.is_type(),
+                type_specifiers: Vec::new(),
+                type_position_only: Vec::new(),
+              });
+            }
+
+            for (name, kind, span) in declaration_bindings(decl) {
+              exports.push(ExportItem::Named(ExportInfo {
+                local: name.clone(),
+                name,
+                source: None,
+                kind,
+                span,
+                ..Default::default()
+              }));
+            }
+          }
+
+          for spec in &export.specifiers {
+            let local = spec.local.name().to_string();
+
+            exports.push(ExportItem::Named(ExportInfo {
+              name: spec.exported.name().to_string(),
+              kind: local_kinds.get(&local).copied().unwrap_or_default(),
+              local,
+              source: None,
+              type_only: export.export_kind.is_type() || spec.export_kind.is_type(),
+              span: spec.exported.span(),
+              ..Default::default()
+            }));
+          }
+        }
+      }
+
+      Statement::ExportDefaultDeclaration(export) => {
+        let local = match &export.declaration {
+          ExportDefaultDeclarationKind::Identifier(id) => Some(id.name.to_string()),
+          ExportDefaultDeclarationKind::FunctionDeclaration(fd) => {
+            fd.id.as_ref().map(|id| id.name.to_string())
+          }
+          ExportDefaultDeclarationKind::ClassDeclaration(cd) => {
+            cd.id.as_ref().map(|id| id.name.to_string())
+          }
+          ExportDefaultDeclarationKind::TSInterfaceDeclaration(int) => {
+            Some(int.id.name.to_string())
+          }
+          _ => None,
+        };
+
+        exports.push(ExportItem::Named(ExportInfo {
+          name: "default".to_string(),
+          local: local.unwrap_or_else(|| "default".to_string()),
+          source: None,
+          kind: ExportKind::Default,
+          span: export.span,
+          ..Default::default()
+        }));
+      }
+
+      // `import x = require('./x')`
+      Statement::TSImportEqualsDeclaration(import_equals) => {
+        if let TSModuleReference::ExternalModuleReference(reference) =
+          &import_equals.module_reference
+        {
+          imports.push(ImportInfo {
+            source: reference.expression.value.to_string(),
+            kind: ImportKind::ImportEquals,
+            specifiers: Vec::new(),
+            has_namespace: true,
+            has_default: false,
+            namespace_members: None,
+            type_only: import_equals.import_kind.is_type(),
+            type_specifiers: Vec::new(),
+            type_position_only: Vec::new(),
+          });
+        }
+      }
+
+      // `export = value`
+      Statement::TSExportAssignment(assignment) => {
+        exports.push(ExportItem::Named(ExportInfo {
+          name: "default".to_string(),
+          local: identifier_name(&assignment.expression).unwrap_or_else(|| "default".to_string()),
+          source: None,
+          kind: ExportKind::Default,
+          span: assignment.span,
+          ..Default::default()
+        }));
+      }
+
+      Statement::ExportAllDeclaration(export_all) => {
+        let source_path = PathBuf::from(export_all.source.value.to_string());
+
+        match &export_all.exported {
+          Some(exported) => exports.push(ExportItem::Namespace(ExportInfo {
+            name: exported.name().to_string(),
+            local: "*".to_string(),
+            source: Some(source_path),
+            kind: ExportKind::ReExport,
+            type_only: export_all.export_kind.is_type(),
+            span: exported.span(),
+            ..Default::default()
+          })),
+          None => exports.push(ExportItem::All(source_path)),
+        }
+      }
+
+      // `export as namespace Lib` names the UMD global, nothing can import it
+      Statement::TSNamespaceExportDeclaration(_) => {}
+
+      _ => {}
+    }
+  }
+
+  // `import { a } from './a'; export { a }` re-exports `a` just like `export { a } from './a'`,
+  // and `import * as ns from './a'; export { ns }` is `export * as ns from './a'`.
+  // When the binding is not referenced anywhere else, the import alone does not use it.
+  let semantic = SemanticBuilder::new().build(program).semantic;
+  // Scopes holding the module's top-level bindings: the script blocks of a Vue file
+  let vue_scopes: Vec<ScopeId> = vue_script_bodies(program)
+    .iter()
+    .filter_map(|arrow| arrow.scope_id.get())
+    .collect();
+  let module_scopes = if vue_scopes.is_empty() {
+    vec![semantic.scoping().root_scope_id()]
+  } else {
+    vue_scopes
+  };
+  let mut references = ReferenceCollector::default();
+  references.visit_program(program);
+
+  for export in &mut exports {
+    if let ExportItem::Named(exp) = export
+      && exp.source.is_none()
+      && let Some((index, imported)) = import_bindings.get(&exp.local)
+    {
+      let import = &mut imports[*index];
+      exp.type_only |= import.type_only || import.type_specifiers.contains(imported);
+
+      if !references.names.contains(&exp.local) {
+        match imported.as_str() {
+          "default" => import.has_default = false,
+          "*" => import.has_namespace = false,
+          _ => {
+            import.specifiers.retain(|s| s != imported);
+            import.type_specifiers.retain(|s| s != imported);
+          }
+        }
+      }
+
+      exp.source = Some(PathBuf::from(&import.source));
+      exp.local = imported.clone();
+      exp.kind = ExportKind::ReExport;
+      if imported == "*" {
+        *export = ExportItem::Namespace(exp.clone());
+      }
+    }
+  }
+
+  // Named and default imports only referenced in type positions
+  let mut positions = TypePositionCollector::default();
+  positions.visit_program(program);
+
+  for (local, (index, imported)) in &import_bindings {
+    let import = &mut imports[*index];
+    let still_imported = match imported.as_str() {
+      "*" => false,
+      "default" => import.has_default,
+      _ => import.specifiers.contains(imported),
+    };
+
+    if still_imported
+      && !import.is_type_use(imported)
+      && positions.type_refs.contains(local)
+      && !positions.value_refs.contains(local)
+    {
+      import.type_position_only.push(imported.clone());
+    }
+  }
+
+  // Narrow `import * as ns` down to the members actually read off `ns`
+  let mut namespace_usage = NamespaceUsageCollector::default();
+  for (local, (index, imported)) in &import_bindings {
+    if imported == "*" && imports[*index].has_namespace {
+      namespace_usage.members.insert(local.clone(), Vec::new());
+    }
+  }
+
+  if !namespace_usage.members.is_empty() {
+    namespace_usage.visit_program(program);
+
+    // A namespace never referenced keeps counting as a use of the whole module
+    for (local, mut members) in namespace_usage.members {
+      if members.is_empty() || namespace_usage.escaped.contains(&local) {
+        continue;
+      }
+      members.sort();
+      members.dedup();
+      imports[import_bindings[&local].0].namespace_members = Some(members);
+    }
+  }
+
+  let mut dynamic_imports = DynamicImportCollector::default();
+  dynamic_imports.visit_program(program);
+  imports.extend(dynamic_imports.imports);
+
+  let mut commonjs = CommonJsCollector::default();
+  commonjs.visit_program(program);
+  imports.extend(commonjs.imports);
+  let is_commonjs = !commonjs.exports.is_empty();
+  exports.extend(commonjs.exports);
+
+  let mut globs = GlobImportCollector::default();
+  globs.visit_program(program);
+
+  let mut specifiers = SpecifierPositionCollector::default();
+  specifiers.visit_program(program);
+
+  // Overloads and merged declarations (`interface A` next to `const A`) export a name once,
+  // as the value when a type and a value share it
+  let mut seen: HashMap<String, usize> = HashMap::new();
+  let mut merged: Vec<ExportItem> = Vec::with_capacity(exports.len());
+  for export in exports {
+    let (ExportItem::Named(exp) | ExportItem::Namespace(exp)) = &export else {
+      merged.push(export);
+      continue;
+    };
+    match seen.get(&exp.name) {
+      None => {
+        seen.insert(exp.name.clone(), merged.len());
+        merged.push(export);
+      }
+      Some(&index) => {
+        if let ExportItem::Named(kept) | ExportItem::Namespace(kept) = &merged[index]
+          && kept.declares_type()
+          && !exp.declares_type()
+        {
+          merged[index] = export;
+        }
+      }
+    }
+  }
+  let mut exports = merged;
+
+  let line_index = LineIndex::new(program.source_text);
+  for export in &mut exports {
+    if let ExportItem::Named(exp) | ExportItem::Namespace(exp) = export {
+      (exp.line, exp.column) = line_index.position(program.source_text, exp.span.start);
+      exp.used_in_file = exp.source.is_none()
+        && references.uses_outside_declaration(&semantic, &module_scopes, &exp.local);
+    }
+  }
+
+  ParsedFile {
+    imports,
+    exports,
+    globs: globs.globs,
+    is_commonjs,
+    specifier_positions: specifiers
+      .offsets
+      .into_iter()
+      .map(|(spec, offset)| (spec, line_index.position(program.source_text, offset)))
+      .collect(),
+  }
+}
+
+/// Names bound by a declaration, with their kind and the span of the name
+fn declaration_bindings(decl: &Declaration) -> Vec<(String, ExportKind, Span)> {
+  let binding = |id: &BindingIdentifier, kind| vec![(id.name.to_string(), kind, id.span)];
+
+  match decl {
+    Declaration::TSInterfaceDeclaration(int) => binding(&int.id, ExportKind::Interface),
+    Declaration::TSTypeAliasDeclaration(ta) => binding(&ta.id, ExportKind::Type),
+    Declaration::TSEnumDeclaration(en) => binding(&en.id, ExportKind::Enum),
+    // `declare module 'foo' {}` augments another module instead of declaring a binding
+    Declaration::TSModuleDeclaration(md) => match &md.id {
+      TSModuleDeclarationName::Identifier(id) => binding(id, ExportKind::Namespace),
+      TSModuleDeclarationName::StringLiteral(_) => Vec::new(),
+    },
+    // `import A = NS.B` and `import A = require('./a')`
+    Declaration::TSImportEqualsDeclaration(ie) => binding(&ie.id, ExportKind::Namespace),
+    Declaration::FunctionDeclaration(fd) => fd
+      .id
+      .as_ref()
+      .map(|id| binding(id, ExportKind::Function))
+      .unwrap_or_default(),
+    // Every binding of `const { a, b: [c] } = obj`
+    Declaration::VariableDeclaration(vd) => vd
+      .declarations
+      .iter()
+      .flat_map(|declarator| declarator.id.get_binding_identifiers())
+      .map(|id| (id.name.to_string(), ExportKind::Variable, id.span))
+      .collect(),
+    Declaration::ClassDeclaration(cd) => cd
+      .id
+      .as_ref()
+      .map(|id| binding(id, ExportKind::Class))
+      .unwrap_or_default(),
+    // `declare global {}` augments the global scope
+    Declaration::TSGlobalDeclaration(_) => Vec::new(),
+  }
+}
+
+/// Byte offsets of line starts, to turn spans into line/column positions
+struct LineIndex {
+  line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+  fn new(source: &str) -> Self {
+    let line_starts = std::iter::once(0)
+      .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+      .collect();
+    Self { line_starts }
+  }
+
+  /// 1-based line and UTF-16 column of a byte offset
+  fn position(&self, source: &str, offset: u32) -> (u32, u32) {
+    let offset = (offset as usize).min(source.len());
+    let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+    let column = source
+      .get(self.line_starts[line]..offset)
+      .map_or(0, |prefix| prefix.encode_utf16().count());
+    (line as u32 + 1, column as u32 + 1)
+  }
+}
+
+/// Where each module specifier first appears, for reporting imports that do not resolve
+#[derive(Default)]
+struct SpecifierPositionCollector {
+  offsets: HashMap<String, u32>,
+}
+
+impl SpecifierPositionCollector {
+  fn record(&mut self, spec: &str, span: Span) {
+    self.offsets.entry(spec.to_string()).or_insert(span.start);
+  }
+}
+
+impl<'a> Visit<'a> for SpecifierPositionCollector {
+  fn visit_import_declaration(&mut self, it: &ImportDeclaration<'a>) {
+    self.record(&it.source.value, it.source.span);
+  }
+
+  fn visit_export_named_declaration(&mut self, it: &ExportNamedDeclaration<'a>) {
+    if let Some(source) = &it.source {
+      self.record(&source.value, source.span);
+    }
+    walk::walk_export_named_declaration(self, it);
+  }
+
+  fn visit_export_all_declaration(&mut self, it: &ExportAllDeclaration<'a>) {
+    self.record(&it.source.value, it.source.span);
+  }
+
+  fn visit_ts_external_module_reference(&mut self, it: &TSExternalModuleReference<'a>) {
+    self.record(&it.expression.value, it.expression.span);
+  }
+
+  fn visit_import_expression(&mut self, it: &ImportExpression<'a>) {
+    if let Some(spec) = static_specifier(&it.source) {
+      self.record(&spec, it.source.span());
+    }
+    walk::walk_import_expression(self, it);
+  }
+
+  fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+    if it.is_require_call()
+      && let Some(arg) = it.arguments.first().and_then(|arg| arg.as_expression())
+      && let Some(spec) = static_specifier(arg)
+    {
+      self.record(&spec, arg.span());
+    } else if is_vite_glob(it) || is_require_context(it) {
+      // Globs are keyed by their first pattern or directory, like `GlobImport::source`
+      let first = match it.arguments.first() {
+        Some(Argument::ArrayExpression(array)) => {
+          array.elements.first().and_then(|e| e.as_expression())
+        }
+        Some(arg) => arg.as_expression(),
+        None => None,
+      };
+      if let Some(first) = first
+        && let Some(spec) = static_specifier(first)
+      {
+        self.record(&spec, first.span());
+      }
+    }
+    walk::walk_call_expression(self, it);
+  }
+}
+
+/// `import.meta.glob()`
+fn is_vite_glob(call: &CallExpression) -> bool {
+  call.callee.as_member_expression().is_some_and(|member| {
+    matches!(
+      member.object(),
+      Expression::MetaProperty(meta) if meta.meta.name == "import" && meta.property.name == "meta"
+    ) && member.static_property_name() == Some("glob")
+  })
+}
+
+/// `require.context()`
+fn is_require_context(call: &CallExpression) -> bool {
+  call.callee.as_member_expression().is_some_and(|member| {
+    member.object().is_specific_id("require") && member.static_property_name() == Some("context")
+  })
+}
+
+/// `import.meta.glob()` and `require.context()` calls with static arguments
+#[derive(Default)]
+struct GlobImportCollector {
+  globs: Vec<GlobImport>,
+}
+
+impl GlobImportCollector {
+  /// `import.meta.glob(patterns, { import })`
+  fn push_vite_glob(&mut self, call: &CallExpression) {
+    let patterns: Option<Vec<String>> = match call.arguments.first() {
+      Some(Argument::ArrayExpression(array)) => array
+        .elements
+        .iter()
+        .map(|e| e.as_expression().and_then(static_specifier))
+        .collect(),
+      Some(arg) => arg
+        .as_expression()
+        .and_then(static_specifier)
+        .map(|p| vec![p]),
+      None => None,
+    };
+    let Some(patterns) = patterns.filter(|p| !p.is_empty()) else {
+      return;
+    };
+
+    // `import: 'default'` picks a single export of every match, `'*'` the whole module
+    let import = match call.arguments.get(1) {
+      Some(Argument::ObjectExpression(options)) => {
+        options
+          .properties
+          .iter()
+          .find_map(|property| match property {
+            ObjectPropertyKind::ObjectProperty(prop)
+              if prop.key.static_name().as_deref() == Some("import") =>
+            {
+              static_specifier(&prop.value)
+            }
+            _ => None,
+          })
+      }
+      _ => None,
+    };
+
+    self.globs.push(GlobImport {
+      source: patterns[0].clone(),
+      pattern: GlobPattern::Glob(patterns),
+      namespace_members: import.filter(|name| name != "*").map(|name| vec![name]),
+    });
+  }
+
+  /// `require.context(directory, recursive = true, regex = /^\.\/.*$/)`
+  fn push_require_context(&mut self, call: &CallExpression) {
+    let Some(directory) = call
+      .arguments
+      .first()
+      .and_then(|arg| arg.as_expression())
+      .and_then(static_specifier)
+    else {
+      return;
+    };
+
+    let recursive = match call.arguments.get(1) {
+      Some(Argument::BooleanLiteral(lit)) => lit.value,
+      _ => true,
+    };
+
+    let regex = match call.arguments.get(2) {
+      Some(Argument::RegExpLiteral(lit)) if lit.regex.flags.contains(RegExpFlags::I) => {
+        format!("(?i){}", lit.regex.pattern.text)
+      }
+      Some(Argument::RegExpLiteral(lit)) => lit.regex.pattern.text.to_string(),
+      _ => r"^\./.*$".to_string(),
+    };
+
+    self.globs.push(GlobImport {
+      source: directory.clone(),
+      pattern: GlobPattern::Context {
+        directory,
+        recursive,
+        regex,
+      },
+      namespace_members: None,
+    });
+  }
+}
+
+impl<'a> Visit<'a> for GlobImportCollector {
+  fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+    if is_vite_glob(it) {
+      self.push_vite_glob(it);
+    } else if is_require_context(it) {
+      self.push_require_context(it);
+    }
+    walk::walk_call_expression(self, it);
+  }
+}
+
+/// Name of a plain identifier expression
+fn identifier_name(expr: &Expression) -> Option<String> {
+  match expr.without_parentheses() {
+    Expression::Identifier(id) => Some(id.name.to_string()),
+    _ => None,
+  }
+}
+
+/// `require()` calls and `module.exports`/`exports` assignments anywhere in a module
+#[derive(Default)]
+struct CommonJsCollector {
+  imports: Vec<ImportInfo>,
+  exports: Vec<ExportItem>,
+}
+
+impl CommonJsCollector {
+  fn push_require(&mut self, call: &CallExpression, members: Option<Vec<String>>) {
+    if let Some(source) = call
+      .arguments
+      .first()
+      .and_then(|arg| arg.as_expression())
+      .and_then(static_specifier)
+    {
+      self.imports.push(ImportInfo {
+        source,
+        kind: ImportKind::Require,
+        specifiers: Vec::new(),
+        has_namespace: true,
+        has_default: false,
+        namespace_members: members,
+        type_only: false,
+        type_specifiers: Vec::new(),
+        type_position_only: Vec::new(),
+      });
+    }
+  }
+
+  fn push_export(&mut self, name: &str, value: &Expression, kind: ExportKind, span: Span) {
+    self.exports.push(ExportItem::Named(ExportInfo {
+      name: name.to_string(),
+      local: identifier_name(value).unwrap_or_else(|| name.to_string()),
+      source: None,
+      kind,
+      span,
+      ..Default::default()
+    }));
+  }
+}
+
+/// Kind of a value assigned to a CommonJS export
+fn value_kind(value: &Expression) -> ExportKind {
+  match value.without_parentheses() {
+    Expression::FunctionExpression(_) | Expression::ArrowFunctionExpression(_) => {
+      ExportKind::Function
+    }
+    Expression::ClassExpression(_) => ExportKind::Class,
+    _ => ExportKind::Variable,
+  }
+}
+
+/// `require('./x')`, possibly parenthesized
+fn as_require_call<'b, 'a>(expr: &'b Expression<'a>) -> Option<&'b CallExpression<'a>> {
+  match expr.without_parentheses() {
+    Expression::CallExpression(call) if call.is_require_call() => Some(call),
+    _ => None,
+  }
+}
+
+impl<'a> Visit<'a> for CommonJsCollector {
+  fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+    if it.is_require_call() {
+      self.push_require(it, None);
+      return;
+    }
+    walk::walk_call_expression(self, it);
+  }
+
+  // `const { foo } = require('./x')`
+  fn visit_variable_declarator(&mut self, it: &VariableDeclarator<'a>) {
+    if let Some(call) = it.init.as_ref().and_then(as_require_call)
+      && let BindingPatternKind::ObjectPattern(pattern) = &it.id.kind
+      && let Some(names) = object_pattern_names(pattern)
+    {
+      self.push_require(call, Some(names));
+      self.visit_binding_pattern(&it.id);
+      return;
+    }
+    walk::walk_variable_declarator(self, it);
+  }
+
+  // `require('./x').foo`
+  fn visit_member_expression(&mut self, it: &MemberExpression<'a>) {
+    if let Some(call) = as_require_call(it.object())
+      && let Some(member) = it.static_property_name()
+    {
+      self.push_require(call, Some(vec![member.to_string()]));
+      return;
+    }
+    walk::walk_member_expression(self, it);
+  }
+
+  // `module.exports = ...`, `module.exports.foo = ...` and `exports.foo = ...`
+  fn visit_assignment_expression(&mut self, it: &AssignmentExpression<'a>) {
+    if let Some(target) = it.left.as_member_expression() {
+      match commonjs_export_target(target) {
+        Some(None) => match it.right.without_parentheses() {
+          Expression::ObjectExpression(object) => {
+            for property in &object.properties {
+              if let ObjectPropertyKind::ObjectProperty(prop) = property
+                && let Some(name) = prop.key.static_name()
+              {
+                self.push_export(&name, &prop.value, value_kind(&prop.value), prop.key.span());
+              }
+            }
+          }
+          right => self.push_export("default", right, ExportKind::Default, target.span()),
+        },
+        Some(Some(name)) => self.push_export(name, &it.right, value_kind(&it.right), target.span()),
+        None => {}
+      }
+    }
+    walk::walk_assignment_expression(self, it);
+  }
+}
+
+/// `module.exports` as `Some(None)`, `exports.name` and `module.exports.name` as `Some(Some(name))`
+fn commonjs_export_target<'a>(target: &MemberExpression<'a>) -> Option<Option<&'a str>> {
+  let object = target.object();
+  let property = target.static_property_name();
+
+  if object.is_specific_id("module") && property == Some("exports") {
+    Some(None)
+  } else if object.is_specific_id("exports")
+    || object.is_specific_member_access("module", "exports")
+  {
+    property.map(Some)
+  } else {
+    None
+  }
+}
+
+/// Calls wrapping `() => import('./x')` to load its default export
+const LAZY_WRAPPERS: &[&str] = &["lazy", "defineAsyncComponent"];
+
+/// `import()` expressions with a static specifier, anywhere in a module. Their result is
+/// treated like a namespace import, narrowed to the members read when the pattern allows.
+#[derive(Default)]
+struct DynamicImportCollector {
+  imports: Vec<ImportInfo>,
+}
+
+impl DynamicImportCollector {
+  fn push(&mut self, import: &ImportExpression, members: Option<Vec<String>>) {
+    if let Some(source) = static_specifier(&import.source) {
+      self.imports.push(ImportInfo {
+        source,
+        kind: ImportKind::Dynamic,
+        specifiers: Vec::new(),
+        has_namespace: true,
+        has_default: false,
+        namespace_members: members,
+        type_only: false,
+        type_specifiers: Vec::new(),
+        type_position_only: Vec::new(),
+      });
+    }
+  }
+}
+
+impl<'a> Visit<'a> for DynamicImportCollector {
+  fn visit_import_expression(&mut self, it: &ImportExpression<'a>) {
+    self.push(it, None);
+    walk::walk_import_expression(self, it);
+  }
+
+  // `const { foo } = await import('./x')`
+  fn visit_variable_declarator(&mut self, it: &VariableDeclarator<'a>) {
+    if let Some(import) = it.init.as_ref().and_then(as_dynamic_import)
+      && let BindingPatternKind::ObjectPattern(pattern) = &it.id.kind
+      && let Some(names) = object_pattern_names(pattern)
+    {
+      self.push(import, Some(names));
+      self.visit_binding_pattern(&it.id);
+      return;
+    }
+    walk::walk_variable_declarator(self, it);
+  }
+
+  // `import('./x').then(m => m.foo)` and `lazy(() => import('./x'))`
+  fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+    if let Some(member) = it.callee.as_member_expression()
+      && member.static_property_name() == Some("then")
+      && let Some(import) = as_dynamic_import(member.object())
+    {
+      let members = it
+        .arguments
+        .first()
+        .and_then(|arg| arg.as_expression())
+        .and_then(callback_members);
+      self.push(import, members);
+      for arg in &it.arguments {
+        self.visit_argument(arg);
+      }
+      return;
+    }
+
+    if it
+      .callee_name()
+      .is_some_and(|name| LAZY_WRAPPERS.contains(&name))
+      && let Some(Argument::ArrowFunctionExpression(arrow)) = it.arguments.first()
+      && let Some(import) = arrow.get_expression().and_then(as_dynamic_import)
+    {
+      self.push(import, Some(vec!["default".to_string()]));
+      return;
+    }
+
+    walk::walk_call_expression(self, it);
+  }
+}
+
+/// `import('./x')`, possibly awaited or parenthesized
+fn as_dynamic_import<'b, 'a>(expr: &'b Expression<'a>) -> Option<&'b ImportExpression<'a>> {
+  match expr.without_parentheses() {
+    Expression::ImportExpression(import) => Some(import),
+    Expression::AwaitExpression(await_expr) => as_dynamic_import(&await_expr.argument),
+    _ => None,
+  }
+}
+
+/// Specifier given as a string literal or a template literal without substitutions
+fn static_specifier(expr: &Expression) -> Option<String> {
+  match expr {
+    Expression::StringLiteral(lit) => Some(lit.value.to_string()),
+    Expression::TemplateLiteral(lit) => lit.single_quasi().map(|q| q.to_string()),
+    _ => None,
+  }
+}
+
+/// Keys of `{ a, b: c }`, or `None` when a rest element or computed key hides some
+fn object_pattern_names(pattern: &ObjectPattern) -> Option<Vec<String>> {
+  if pattern.rest.is_some() {
+    return None;
+  }
+  pattern
+    .properties
+    .iter()
+    .map(|p| p.key.static_name().map(|n| n.to_string()))
+    .collect()
+}
+
+/// Members read off the module in a `.then()` callback, `None` when it may use any of them
+fn callback_members(callback: &Expression) -> Option<Vec<String>> {
+  let (params, body) = match callback {
+    Expression::ArrowFunctionExpression(arrow) => (&arrow.params, &arrow.body),
+    Expression::FunctionExpression(func) => (&func.params, func.body.as_ref()?),
+    _ => return None,
+  };
+
+  let Some(param) = params.items.first() else {
+    return Some(Vec::new());
+  };
+
+  match &param.pattern.kind {
+    BindingPatternKind::ObjectPattern(pattern) => object_pattern_names(pattern),
+    BindingPatternKind::BindingIdentifier(id) => {
+      let mut usage = NamespaceUsageCollector::default();
+      usage.members.insert(id.name.to_string(), Vec::new());
+      usage.visit_function_body(body);
+
+      if usage.escaped.is_empty() {
+        usage.members.into_values().next()
+      } else {
+        None
+      }
+    }
+    _ => None,
+  }
+}
+
+/// Names referenced in a module, leaving out `export { a }` and `export default a`
+/// which only forward a binding
+#[derive(Default)]
+struct ReferenceCollector {
+  names: HashSet<String>,
+  /// Spans of the visited references, once semantic analysis resolved them
+  spans: HashMap<ReferenceId, Span>,
+}
+
+impl ReferenceCollector {
+  /// Whether the top-level binding `name`, declared in one of `scopes`, is referenced
+  /// outside its own declaration, so shadowing bindings and recursive calls do not count
+  fn uses_outside_declaration(&self, semantic: &Semantic, scopes: &[ScopeId], name: &str) -> bool {
+    let scoping = semantic.scoping();
+    let Some(symbol) = scopes
+      .iter()
+      .find_map(|scope| scoping.get_binding(*scope, name))
+    else {
+      return false;
+    };
+    let declaration = semantic
+      .nodes()
+      .get_node(scoping.symbol_declaration(symbol))
+      .span();
+
+    scoping
+      .get_resolved_reference_ids(symbol)
+      .iter()
+      .filter_map(|id| self.spans.get(id))
+      .any(|span| !declaration.contains_inclusive(*span))
+  }
+}
+
+impl<'a> Visit<'a> for ReferenceCollector {
+  fn visit_identifier_reference(&mut self, it: &IdentifierReference<'a>) {
+    self.names.insert(it.name.to_string());
+    if let Some(id) = it.reference_id.get() {
+      self.spans.insert(id, it.span);
+    }
+  }
+
+  fn visit_export_named_declaration(&mut self, it: &ExportNamedDeclaration<'a>) {
+    if let Some(decl) = &it.declaration {
+      self.visit_declaration(decl);
+    }
+  }
+
+  fn visit_export_default_declaration(&mut self, it: &ExportDefaultDeclaration<'a>) {
+    if !matches!(it.declaration, ExportDefaultDeclarationKind::Identifier(_)) {
+      walk::walk_export_default_declaration(self, it);
+    }
+  }
+
+  // Identifiers in `module.exports = { a }` and `exports.a = a` only forward a binding
+  fn visit_assignment_expression(&mut self, it: &AssignmentExpression<'a>) {
+    if it
+      .left
+      .as_member_expression()
+      .is_none_or(|target| commonjs_export_target(target).is_none())
+    {
+      walk::walk_assignment_expression(self, it);
+      return;
+    }
+
+    match it.right.without_parentheses() {
+      Expression::Identifier(_) => {}
+      Expression::ObjectExpression(object) => {
+        for property in &object.properties {
+          match property {
+            ObjectPropertyKind::ObjectProperty(prop)
+              if matches!(prop.value.without_parentheses(), Expression::Identifier(_)) => {}
+            _ => self.visit_object_property_kind(property),
+          }
+        }
+      }
+      right => self.visit_expression(right),
+    }
+  }
+}
+
+/// Names referenced in value positions and in type positions (annotations, `implements`,
+/// interfaces), leaving out `export { a }` and `export default a` like [`ReferenceCollector`]
+#[derive(Default)]
+struct TypePositionCollector {
+  type_depth: usize,
+  value_refs: HashSet<String>,
+  type_refs: HashSet<String>,
+}
+
+impl TypePositionCollector {
+  fn in_type<F: FnOnce(&mut Self)>(&mut self, walk: F) {
+    self.type_depth += 1;
+    walk(self);
+    self.type_depth -= 1;
+  }
+}
+
+impl<'a> Visit<'a> for TypePositionCollector {
+  fn visit_identifier_reference(&mut self, it: &IdentifierReference<'a>) {
+    if self.type_depth > 0 {
+      self.type_refs.insert(it.name.to_string());
+    } else {
+      self.value_refs.insert(it.name.to_string());
+    }
+  }
+
+  fn visit_ts_type(&mut self, it: &TSType<'a>) {
+    self.in_type(|v| walk::walk_ts_type(v, it));
+  }
+
+  fn visit_ts_class_implements(&mut self, it: &TSClassImplements<'a>) {
+    self.in_type(|v| walk::walk_ts_class_implements(v, it));
+  }
+
+  fn visit_ts_interface_declaration(&mut self, it: &TSInterfaceDeclaration<'a>) {
+    self.in_type(|v| walk::walk_ts_interface_declaration(v, it));
+  }
+
+  fn visit_export_named_declaration(&mut self, it: &ExportNamedDeclaration<'a>) {
+    if let Some(decl) = &it.declaration {
+      self.visit_declaration(decl);
+    }
+  }
+
+  fn visit_export_default_declaration(&mut self, it: &ExportDefaultDeclaration<'a>) {
+    if !matches!(it.declaration, ExportDefaultDeclarationKind::Identifier(_)) {
+      walk::walk_export_default_declaration(self, it);
+    }
+  }
+}
+
+/// Members read off namespace import bindings, and the bindings used any other way
+#[derive(Default)]
+struct NamespaceUsageCollector {
+  members: HashMap<String, Vec<String>>,
+  escaped: HashSet<String>,
+}
+
+impl NamespaceUsageCollector {
+  fn namespace_of<'b>(&self, expr: &'b Expression) -> Option<&'b str> {
+    match expr {
+      Expression::Identifier(id) if self.members.contains_key(id.name.as_str()) => {
+        Some(id.name.as_str())
+      }
+      _ => None,
+    }
+  }
+
+  fn record(&mut self, namespace: &str, member: &str) {
+    if let Some(members) = self.members.get_mut(namespace) {
+      members.push(member.to_string());
+    }
+  }
+}
+
+impl<'a> Visit<'a> for NamespaceUsageCollector {
+  // Any reference not consumed by a member access below lets the namespace object escape
+  fn visit_identifier_reference(&mut self, it: &IdentifierReference<'a>) {
+    if self.members.contains_key(it.name.as_str()) {
+      self.escaped.insert(it.name.to_string());
+    }
+  }
+
+  fn visit_member_expression(&mut self, it: &MemberExpression<'a>) {
+    if let Some(namespace) = self.namespace_of(it.object())
+      && let Some(member) = it.static_property_name()
+    {
+      self.record(namespace, member);
+      return;
+    }
+    walk::walk_member_expression(self, it);
+  }
+
+  fn visit_variable_declarator(&mut self, it: &VariableDeclarator<'a>) {
+    if let Some(init) = &it.init
+      && let Some(namespace) = self.namespace_of(init)
+      && let BindingPatternKind::ObjectPattern(pattern) = &it.id.kind
+      && let Some(names) = object_pattern_names(pattern)
+    {
+      for name in names {
+        self.record(namespace, &name);
+      }
+      self.visit_binding_pattern(&it.id);
+      return;
+    }
+    walk::walk_variable_declarator(self, it);
+  }
+
+  fn visit_ts_qualified_name(&mut self, it: &TSQualifiedName<'a>) {
+    if let TSTypeName::IdentifierReference(id) = &it.left
+      && self.members.contains_key(id.name.as_str())
+    {
+      self.record(&id.name, &it.right.name);
+      return;
+    }
+    walk::walk_ts_qualified_name(self, it);
+  }
+
+  fn visit_export_named_declaration(&mut self, it: &ExportNamedDeclaration<'a>) {
+    if let Some(decl) = &it.declaration {
+      self.visit_declaration(decl);
+    }
+  }
+
+  fn visit_export_default_declaration(&mut self, it: &ExportDefaultDeclaration<'a>) {
+    if !matches!(it.declaration, ExportDefaultDeclarationKind::Identifier(_)) {
+      walk::walk_export_default_declaration(self, it);
+    }
+  }
+}
+
+/// Check if a module specifier is relative
+fn is_relative(spec: &str) -> bool {
+  spec.starts_with('.')
+}
+
+/// Package a bare specifier imports: `lodash/fp` is `lodash` and `@vue/shared/dist/x` is
+/// `@vue/shared`. `None` for relative and absolute paths, aliases, protocols such as
+/// `node:` or `virtual:`, subpath imports (`#internal`) and Node.js built-ins.
+fn package_name(spec: &str, config: &SweepyConfig) -> Option<String> {
+  if is_relative(spec) || spec.starts_with('/') || spec.starts_with('#') || spec.contains(':') {
+    return None;
+  }
+  if is_aliased(spec, config) {
+    return None;
+  }
+
+  let mut segments = spec.split('/');
+  let first = segments.next()?;
+  let name = if let Some(scope) = first.strip_prefix('@') {
+    let package = segments.next().filter(|p| !p.is_empty())?;
+    if scope.is_empty() {
+      return None;
+    }
+    format!("{}/{}", first, package)
+  } else {
+    first.to_string()
+  };
+
+  let valid_start = name
+    .chars()
+    .next()
+    .is_some_and(|c| c == '@' || c.is_ascii_alphanumeric());
+  if !valid_start || NODE_BUILTINS.contains(&name.as_str()) {
+    return None;
+  }
+  Some(name)
+}
+
+/// Package an `@types` package provides types for: `@types/lodash` types `lodash`, and
+/// `@types/babel__core` types `@babel/core`
+fn types_package_target(name: &str) -> Option<String> {
+  let typed = name.strip_prefix("@types/")?;
+  Some(match typed.split_once("__") {
+    Some((scope, package)) => format!("@{}/{}", scope, package),
+    None => typed.to_string(),
+  })
+}
+
+/// Project files matched by a glob import written in `from`
+fn expand_glob(
+  from: &Path,
+  glob: &GlobImport,
+  file_set: &HashSet<PathBuf>,
+  config: &SweepyConfig,
+) -> Vec<PathBuf> {
+  let from_dir = from.parent().unwrap_or(Path::new(""));
+
+  let mut matches: Vec<PathBuf> = match &glob.pattern {
+    GlobPattern::Glob(patterns) => {
+      let mut include = GlobSetBuilder::new();
+      let mut exclude = GlobSetBuilder::new();
+
+      for pattern in patterns {
+        let (set, pattern) = match pattern.strip_prefix('!') {
+          Some(negated) => (&mut exclude, negated),
+          None => (&mut include, pattern.as_str()),
+        };
+        // Vite leaves `**` patterns such as `!**/helpers.js` unanchored, so they match in
+        // any directory. Other bare patterns are taken from the importer.
+        let path = if pattern.starts_with("**") {
+          PathBuf::from(pattern)
+        } else {
+          glob_path(from_dir, pattern, config)
+            .unwrap_or_else(|| normalize_soft(&from_dir.join(pattern)))
+        };
+
+        match GlobBuilder::new(&path.to_string_lossy())
+          .literal_separator(true)
+          .build()
+        {
+          Ok(glob) => {
+            set.add(glob);
+          }
+          Err(err) => eprintln!("Ignoring glob {}: {}", pattern, err),
+        }
+      }
+
+      let (Ok(include), Ok(exclude)) = (include.build(), exclude.build()) else {
+        return Vec::new();
+      };
+      file_set
+        .iter()
+        .filter(|p| include.is_match(p) && !exclude.is_match(p))
+        .cloned()
+        .collect()
+    }
+    GlobPattern::Context {
+      directory,
+      recursive,
+      regex,
+    } => {
+      let Some(directory) = glob_path(from_dir, directory, config) else {
+        return Vec::new();
+      };
+      let regex = match Regex::new(regex) {
+        Ok(regex) => regex,
+        Err(err) => {
+          eprintln!("Ignoring require.context regex {}: {}", regex, err);
+          return Vec::new();
+        }
+      };
+
+      file_set
+        .iter()
+        .filter(|p| {
+          let Ok(relative) = p.strip_prefix(&directory) else {
+            return false;
+          };
+          (*recursive || relative.components().count() == 1)
+            && regex.is_match(&format!("./{}", relative.to_string_lossy()))
+        })
+        .cloned()
+        .collect()
+    }
+  };
+
+  matches.retain(|p| p != from);
+  matches.sort();
+  matches
+}
+
+/// Root-relative path of a glob pattern or context directory: relative to the importer,
+/// root-absolute (`/src/...`), through an alias or through a tsconfig `paths` pattern
+fn glob_path(from_dir: &Path, pattern: &str, config: &SweepyConfig) -> Option<PathBuf> {
+  let path = if is_relative(pattern) {
+    from_dir.join(pattern)
+  } else if let Some(rooted) = pattern.strip_prefix('/') {
+    PathBuf::from(rooted)
+  } else if let Some((alias, target)) = config
+    .alias
+    .iter()
+    .find(|(alias, _)| pattern.starts_with(alias.as_str()))
+  {
+    PathBuf::from(target).join(pattern[alias.len()..].trim_start_matches('/'))
+  } else {
+    let tsconfig = config.tsconfig.as_ref().filter(|t| t.maps(pattern))?;
+    tsconfig.candidates(pattern).into_iter().next()?
+  };
+
+  let path = normalize_soft(&path);
+  if path == Path::new(".") {
+    Some(PathBuf::new())
+  } else {
+    Some(path)
+  }
+}
+
+/// Resolve relative import in memory using a HashSet of file paths
+pub fn resolve_relative_import_from_set(
+  from: &Path,
+  spec: &str,
+  file_set: &HashSet<PathBuf>,
+  config: &SweepyConfig,
+) -> Option<PathBuf> {
+  import_candidates(from, spec, config)
+    .iter()
+    .find_map(|candidate| resolve_candidate(candidate, file_set, config))
+}
+
+/// Paths a specifier may refer to, before extensions and index files are tried
+fn import_candidates(from: &Path, spec: &str, config: &SweepyConfig) -> Vec<PathBuf> {
+  if is_relative(spec) {
+    let from_dir = from.parent().unwrap_or(Path::new(""));
+    vec![normalize_soft(&from_dir.join(spec))]
+  } else {
+    let mut candidates = Vec::new();
+
+    // Check if spec starts with an alias
+    for (alias, target) in &config.alias {
+      if spec.starts_with(alias) {
+        let remainder = &spec[alias.len()..];
+        let remainder = remainder.trim_start_matches('/');
+        candidates.push(normalize_soft(&PathBuf::from(target).join(remainder)));
+        break;
+      }
+    }
+
+    if let Some(tsconfig) = &config.tsconfig {
+      candidates.extend(tsconfig.candidates(spec));
+    }
+
+    if candidates.is_empty() {
+      candidates.push(normalize_soft(Path::new(spec)));
+    }
+    candidates
+  }
+}
+
+/// Whether an alias or tsconfig `paths` pattern maps a specifier into the project.
+/// Aliases match whole segments, so `@` covers `@/utils` but not `@vue/shared`.
+fn is_aliased(spec: &str, config: &SweepyConfig) -> bool {
+  config.alias.keys().any(|alias| {
+    spec
+      .strip_prefix(alias.as_str())
+      .is_some_and(|rest| rest.is_empty() || rest.starts_with('/') || alias.ends_with('/'))
+  }) || config.tsconfig.as_ref().is_some_and(|t| t.maps(spec))
+}
+
+/// Why a relative or aliased specifier that failed to resolve matches no file
+fn unresolved_reason(
+  from: &Path,
+  spec: &str,
+  file_set: &HashSet<PathBuf>,
+  lowercase_set: &HashSet<PathBuf>,
+  config: &SweepyConfig,
+) -> UnresolvedReason {
+  for candidate in import_candidates(from, spec, config) {
+    let lowercase = lowercase_path(&candidate);
+    if resolve_file(&lowercase, lowercase_set)
+      .or_else(|| resolve_index(&lowercase, lowercase_set))
+      .is_some()
+    {
+      return UnresolvedReason::CaseMismatch;
+    }
+
+    let has_source_extension = candidate
+      .extension()
+      .and_then(|ext| ext.to_str())
+      .is_some_and(|ext| SUPPORTED_EXTENSIONS.contains(&ext));
+    if has_source_extension {
+      let stem = candidate.with_extension("");
+      if file_set.iter().any(|file| file.with_extension("") == stem) {
+        return UnresolvedReason::ExtensionMismatch;
+      }
+    }
+  }
+
+  if is_relative(spec) {
+    UnresolvedReason::NoMatchingFile
+  } else {
+    UnresolvedReason::AliasTargetMissing
+  }
+}
+
+fn lowercase_path(path: &Path) -> PathBuf {
+  PathBuf::from(path.to_string_lossy().to_lowercase())
+}
+
+/// Match a candidate path against the file set, as a file first and then as a directory
+fn resolve_candidate(
+  candidate: &Path,
+  file_set: &HashSet<PathBuf>,
+  config: &SweepyConfig,
+) -> Option<PathBuf> {
+  if let Some(file) = resolve_file(candidate, file_set) {
+    return Some(file);
+  }
+
+  // Directory import: package.json `types`/`main`, then `index.*`
+  if let Some(pkg) = config.packages.get(candidate) {
+    for entry in pkg.entry_fields() {
+      let target = normalize_soft(&candidate.join(entry));
+      if let Some(file) =
+        resolve_file(&target, file_set).or_else(|| resolve_index(&target, file_set))
+      {
+        return Some(file);
+      }
+    }
+  }
+
+  resolve_index(candidate, file_set)
+}
+
+/// Extensions appended to a specifier when looking for a file, in resolution order
+const RESOLVE_EXTENSIONS: &[&str] = &[
+  "ts", "tsx", "d.ts", "js", "jsx", "vue", "mts", "cts", "mjs", "cjs",
+];
+
+/// TypeScript sources a JS extension may stand for (NodeNext-style `./utils.js` imports)
+fn ts_source_extensions(ext: &str) -> &'static [&'static str] {
+  match ext {
+    "js" => &["ts", "tsx", "d.ts"],
+    "jsx" => &["tsx"],
+    "mjs" => &["mts", "d.mts"],
+    "cjs" => &["cts", "d.cts"],
+    _ => &[],
+  }
+}
+
+/// Append an extension without touching the existing ones: `user.service` -> `user.service.ts`
+fn append_extension(path: &Path, ext: &str) -> PathBuf {
+  let mut s = path.as_os_str().to_os_string();
+  s.push(".");
+  s.push(ext);
+  PathBuf::from(s)
+}
+
+/// Match a candidate file path, trying supported extensions
+fn resolve_file(candidate: &Path, file_set: &HashSet<PathBuf>) -> Option<PathBuf> {
+  // `./utils.js` prefers `utils.ts` over `utils.js`, like tsc does
+  if let Some(ext) = candidate.extension().and_then(|s| s.to_str()) {
+    for ts_ext in ts_source_extensions(ext) {
+      let source = candidate.with_extension(ts_ext);
+      if file_set.contains(&source) {
+        return Some(source);
+      }
+    }
+  }
+
+  if file_set.contains(candidate) {
+    return Some(candidate.to_path_buf());
+  }
+
+  RESOLVE_EXTENSIONS
+    .iter()
+    .map(|ext| append_extension(candidate, ext))
+    .find(|with_extension| file_set.contains(with_extension))
+}
+
+/// Match `<dir>/index.<ext>` for a directory candidate
+fn resolve_index(dir: &Path, file_set: &HashSet<PathBuf>) -> Option<PathBuf> {
+  RESOLVE_EXTENSIONS
+    .iter()
+    .map(|ext| dir.join(format!("index.{}", ext)))
+    .find(|index| file_set.contains(index))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::collections::HashMap;
+  use std::path::PathBuf;
+
+  /// Test project builder for creating in-memory test projects
+  struct TestProject {
+    sources: HashMap<PathBuf, String>,
+    entries: Vec<PathBuf>,
+  }
+
+  impl TestProject {
+    fn new() -> Self {
+      Self {
+        sources: HashMap::new(),
+        entries: Vec::new(),
+      }
+    }
+
+    fn add_file(mut self, path: &str, content: &str) -> Self {
+      self
+        .sources
+        .insert(PathBuf::from(path), content.to_string());
+      self
+    }
+
+    fn entry(mut self, path: &str) -> Self {
+      self.entries.push(PathBuf::from(path));
+      self
+    }
+
+    fn build(self) -> (ProjectAnalyzer, Vec<PathBuf>) {
+      self.build_with_config(None)
+    }
+
+    fn build_with_config(self, config: Option<SweepyConfig>) -> (ProjectAnalyzer, Vec<PathBuf>) {
+      let sources_ref: HashMap<PathBuf, &str> = self
+        .sources
+        .iter()
+        .map(|(p, c)| (PathBuf::from(p), c.as_str()))
+        .collect();
+
+      let analyzer =
+        ProjectAnalyzer::from_sources(&sources_ref, config).expect("Failed to analyze");
+      (analyzer, self.entries)
+    }
+  }
+
+  fn assert_reachable(analyzer: &ProjectAnalyzer, entries: &[PathBuf], expected: &[&str]) {
+    let reachable = analyzer.compute_reachable(entries.to_vec());
+    for path in expected {
+      assert!(
+        reachable.contains(&PathBuf::from(path)),
+        "Expected {} to be reachable",
+        path
+      );
+    }
+  }
+
+  fn assert_unused(analyzer: &ProjectAnalyzer, expected: Vec<(&str, &str)>) {
+    let mut unused: Vec<_> = analyzer
+      .find_unused_exports()
+      .into_iter()
+      .map(|(file, export)| (file, export.name))
+      .collect();
+    let mut expected_sorted: Vec<_> = expected
+      .into_iter()
+      .map(|(file, name)| (PathBuf::from(file), name.to_string()))
+      .collect();
+    expected_sorted.sort();
+    unused.sort();
+    assert_eq!(unused, expected_sorted);
+  }
+
+  // ===== Basic Named Exports =====
+  mod basic {
+    use super::*;
+
+    #[test]
+    fn named_exports_all_unused() {
+      let project = TestProject::new()
+        .add_file("index.ts", "console.log('entry');")
+        .add_file(
+          "utils.ts",
+          "export const foo = 1;\nexport function bar() {}",
+        )
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts"]);
+      assert_unused(&analyzer, vec![("utils.ts", "bar"), ("utils.ts", "foo")]);
+    }
+
+    #[test]
+    fn named_exports_some_used() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo } from './utils';")
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
+      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+    }
+
+    #[test]
+    fn named_exports_all_used() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo, bar } from './utils';")
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
+      assert_unused(&analyzer, vec![]);
+    }
+
+    #[test]
+    fn type_only_imports() {
+      let project = TestProject::new()
+        .add_file(
+          "index.ts",
+          "import type { MyType } from './types'; import { foo } from './types';",
+        )
+        .add_file(
+          "types.ts",
+          "export type MyType = string;\nexport const foo = 1;\nexport const bar = 2;",
+        )
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "types.ts"]);
+      assert_unused(&analyzer, vec![("types.ts", "bar")]);
+    }
+  }
+
+  // ===== Export Forms =====
+  mod export_forms {
+    use super::*;
+
+    fn unused_of(utils: &str) -> Vec<String> {
+      let project = TestProject::new()
+        .add_file("index.ts", "// no imports")
+        .add_file("utils.ts", utils)
+        .entry("index.ts");
+      let (analyzer, _) = project.build();
+
+      analyzer
+        .find_unused_exports()
+        .into_iter()
+        .map(|(_, export)| export.name)
+        .collect()
+    }
+
+    #[test]
+    fn destructuring_patterns() {
+      assert_eq!(
+        unused_of(
+          "const obj = { a: 1, b: { c: 2 } };\nexport const { a, b: { c: renamed }, ...rest } = obj;\nexport const [x, , y = 1, ...others] = [1, 2, 3];"
+        ),
+        vec!["a", "others", "renamed", "rest", "x", "y"]
+      );
+    }
+
+    #[test]
+    fn declare_and_abstract() {
+      assert_eq!(
+        unused_of(
+          "export declare const version: string;\nexport declare function setup(): void;\nexport declare class Client {}\nexport abstract class Base {}\nexport declare namespace Api { const url: string; }"
+        ),
+        vec!["Api", "Base", "Client", "setup", "version"]
+      );
+    }
+
+    #[test]
+    fn function_overloads_export_once() {
+      assert_eq!(
+        unused_of(
+          "export function parse(value: string): number;\nexport function parse(value: number): number;\nexport function parse(value: unknown) { return Number(value); }"
+        ),
+        vec!["parse"]
+      );
+    }
+
+    #[test]
+    fn import_equals_export() {
+      assert_eq!(
+        unused_of(
+          "namespace Shapes { export const circle = 1; }\nexport import Circle = Shapes.circle;"
+        ),
+        vec!["Circle"]
+      );
+    }
+
+    #[test]
+    fn ambient_declarations_are_not_exports() {
+      assert_eq!(
+        unused_of(
+          "export {};\ndeclare global { interface Window { app: string } }\ndeclare module 'vue' { interface Foo {} }\nexport as namespace MyLib;\nexport const kept = 1;"
+        ),
+        vec!["kept"]
+      );
+    }
+
+    #[test]
+    fn string_export_names() {
+      assert_eq!(
+        unused_of("const a = 1;\nexport { a as 'kebab-name' };"),
+        vec!["kebab-name"]
+      );
+    }
+  }
+
+  // ===== Positions and Kinds =====
+  mod positions {
+    use super::*;
+
+    fn unused_in(file: &str, source: &str) -> Vec<(String, ExportKind, u32, u32, u32, u32)> {
+      let project = TestProject::new()
+        .add_file("index.ts", "// no imports")
+        .add_file(file, source)
+        .entry("index.ts");
+      let (analyzer, _) = project.build();
+
+      analyzer
+        .find_unused_exports()
+        .into_iter()
+        .map(|(_, e)| (e.name, e.kind, e.line, e.column, e.span.start, e.span.end))
+        .collect()
+    }
+
+    #[test]
+    fn declaration_positions() {
+      assert_eq!(
+        unused_in(
+          "utils.ts",
+          "// header\nexport const foo = 1;\n  export function bar() {}"
+        ),
+        vec![
+          ("bar".to_string(), ExportKind::Function, 3, 19, 50, 53),
+          ("foo".to_string(), ExportKind::Variable, 2, 14, 23, 26),
+        ]
+      );
+    }
+
+    #[test]
+    fn columns_count_utf16_units() {
+      assert_eq!(
+        unused_in("utils.ts", "const é = '😀'; export const foo = 1;"),
+        vec![("foo".to_string(), ExportKind::Variable, 1, 30, 32, 35)]
+      );
+    }
+
+    #[test]
+    fn vue_positions_are_relative_to_the_sfc() {
+      assert_eq!(
+        unused_in(
+          "comp.vue",
+          "<template>\n  <div />\n</template>\n<script lang=\"ts\">\nexport const foo = 1;\n</script>"
+        ),
+        vec![("foo".to_string(), ExportKind::Variable, 5, 14, 65, 68)]
+      );
+    }
+
+    #[test]
+    fn export_kinds() {
+      let kinds: Vec<(String, ExportKind)> = unused_in(
+        "utils.ts",
+        "export function f() {}\nexport class C {}\nexport const v = 1;\nexport type T = string;\nexport interface I {}\nexport enum E {}\nexport namespace N {}\nexport default 1;\nexport { x } from './x';\nexport * as ns from './x';\ninterface Local {}\nexport { Local };",
+      )
+      .into_iter()
+      .map(|(name, kind, ..)| (name, kind))
+      .collect();
+
+      assert_eq!(
+        kinds,
+        vec![
+          ("C".to_string(), ExportKind::Class),
+          ("E".to_string(), ExportKind::Enum),
+          ("I".to_string(), ExportKind::Interface),
+          ("Local".to_string(), ExportKind::Interface),
+          ("N".to_string(), ExportKind::Namespace),
+          ("T".to_string(), ExportKind::Type),
+          ("default".to_string(), ExportKind::Default),
+          ("f".to_string(), ExportKind::Function),
+          ("ns".to_string(), ExportKind::ReExport),
+          ("v".to_string(), ExportKind::Variable),
+          ("x".to_string(), ExportKind::ReExport),
+        ]
+      );
+    }
+
+    #[test]
+    fn commonjs_kinds() {
+      let kinds: Vec<(String, ExportKind)> = unused_in(
+        "utils.js",
+        "exports.run = () => {};\nexports.Client = class {};\nexports.port = 80;",
+      )
+      .into_iter()
+      .map(|(name, kind, ..)| (name, kind))
+      .collect();
+
+      assert_eq!(
+        kinds,
+        vec![
+          ("Client".to_string(), ExportKind::Class),
+          ("port".to_string(), ExportKind::Variable),
+          ("run".to_string(), ExportKind::Function),
+        ]
+      );
+    }
+  }
+
+  // ===== Type Exports =====
+  mod type_exports {
+    use super::*;
+
+    /// Unused (values, types) names
+    fn split_unused(analyzer: &ProjectAnalyzer) -> (Vec<String>, Vec<String>) {
+      let (types, values): (Vec<_>, Vec<_>) = analyzer
+        .find_unused_exports()
+        .into_iter()
+        .partition(|(file, export)| analyzer.is_type_export(file, export));
+      let names = |list: Vec<(PathBuf, ExportInfo)>| {
+        list
+          .into_iter()
+          .map(|(file, export)| format!("{}:{}", file.display(), export.name))
+          .collect()
+      };
+      (names(values), names(types))
+    }
+
+    #[test]
+    fn declarations() {
+      let project = TestProject::new()
+        .add_file("index.ts", "// no imports")
+        .add_file(
+          "utils.ts",
+          "export type T = string;\nexport interface I {}\nexport const v = 1;\nexport enum E {}\nexport class C {}",
+        )
+        .entry("index.ts");
+      let (analyzer, _) = project.build();
+
+      assert_eq!(
+        split_unused(&analyzer),
+        (
+          vec![
+            "utils.ts:C".into(),
+            "utils.ts:E".into(),
+            "utils.ts:v".into()
+          ],
+          vec!["utils.ts:I".into(), "utils.ts:T".into()],
+        )
+      );
+    }
+
+    #[test]
+    fn merged_type_and_value() {
+      let project = TestProject::new()
+        .add_file("index.ts", "// no imports")
+        .add_file(
+          "utils.ts",
+          "export interface A {}\nexport const A = 1;\nexport type B = string;\nexport function B() {}\nexport interface C {}\nexport interface C { x: 1 }",
+        )
+        .entry("index.ts");
+      let (analyzer, _) = project.build();
+
+      assert_eq!(
+        split_unused(&analyzer),
+        (
+          vec!["utils.ts:A".into(), "utils.ts:B".into()],
+          vec!["utils.ts:C".into()]
+        )
+      );
+      let kinds: Vec<_> = analyzer.files[Path::new("utils.ts")]
+        .exports
+        .iter()
+        .filter_map(|export| match export {
+          ExportItem::Named(exp) => Some((exp.name.as_str(), exp.kind.as_str(), exp.line)),
+          _ => None,
+        })
+        .collect();
+      assert_eq!(
+        kinds,
+        vec![
+          ("A", "variable", 2),
+          ("B", "function", 4),
+          ("C", "interface", 5)
+        ]
+      );
+    }
+
+    #[test]
+    fn type_only_exports() {
+      let project = TestProject::new()
+        .add_file("index.ts", "// no imports")
+        .add_file(
+          "utils.ts",
+          "class A {}\nclass B {}\nconst c = 1;\nexport type { A };\nexport { type B, c };",
+        )
+        .entry("index.ts");
+      let (analyzer, _) = project.build();
+
+      assert_eq!(
+        split_unused(&analyzer),
+        (
+          vec!["utils.ts:c".into()],
+          vec!["utils.ts:A".into(), "utils.ts:B".into()]
+        )
+      );
+    }
+
+    #[test]
+    fn reexported_types_follow_the_declaration() {
+      let project = TestProject::new()
+        .add_file("index.ts", "// no imports")
+        .add_file(
+          "barrel.ts",
+          "export { Props, render } from './component';\nexport * from './types';\nexport { Model as Entity } from './inner';",
+        )
+        .add_file("inner.ts", "export * from './types';")
+        .add_file(
+          "component.ts",
+          "export interface Props {}\nexport function render() {}",
+        )
+        .add_file("types.ts", "export type Model = { id: string };")
+        .entry("index.ts");
+      let (analyzer, _) = project.build();
+
+      let (values, types) = split_unused(&analyzer);
+      assert_eq!(values, vec!["barrel.ts:render", "component.ts:render"]);
+      assert_eq!(
+        types,
+        vec![
+          "barrel.ts:Entity",
+          "barrel.ts:Props",
+          "component.ts:Props",
+          "types.ts:Model"
+        ]
+      );
+    }
+
+    #[test]
+    fn import_type_then_export() {
+      let project = TestProject::new()
+        .add_file("index.ts", "// no imports")
+        .add_file(
+          "barrel.ts",
+          "import type { Config } from './config';\nimport { type Options, load } from './config';\nexport { Config, Options, load };",
+        )
+        .add_file(
+          "config.ts",
+          "export class Config {}\nexport class Options {}\nexport function load() {}",
+        )
+        .entry("index.ts");
+      let (analyzer, _) = project.build();
+
+      let (values, types) = split_unused(&analyzer);
+      assert_eq!(
+        values,
+        vec![
+          "barrel.ts:load",
+          "config.ts:Config",
+          "config.ts:Options",
+          "config.ts:load"
+        ]
+      );
+      assert_eq!(types, vec!["barrel.ts:Config", "barrel.ts:Options"]);
+    }
+
+    #[test]
+    fn type_only_imports_are_tracked() {
+      let project = TestProject::new()
+        .add_file(
+          "index.ts",
+          "import type { A } from './utils';\nimport { type B, C } from './utils';\nimport type D from './utils';",
+        )
+        .add_file("utils.ts", "export type A = 1;")
+        .entry("index.ts");
+      let (analyzer, _) = project.build();
+
+      let imports = &analyzer.files[Path::new("index.ts")].imports;
+      let flags: Vec<_> = imports
+        .iter()
+        .map(|i| (i.type_only, i.type_specifiers.clone()))
+        .collect();
+      assert_eq!(
+        flags,
+        vec![
+          (true, vec!["A".to_string()]),
+          (false, vec!["B".to_string()]),
+          (true, vec![]),
+        ]
+      );
+    }
+  }
+
+  // ===== Type-only Usage =====
+  mod type_only_usage {
+    use super::*;
+
+    fn type_only_used(analyzer: &ProjectAnalyzer) -> Vec<(String, Vec<String>)> {
+      analyzer
+        .find_type_only_used_exports(None)
+        .into_iter()
+        .map(|(file, export, importers)| {
+          (
+            format!("{}:{}", file.display(), export.name),
+            importers
+              .into_iter()
+              .map(|p| p.display().to_string())
+              .collect(),
+          )
+        })
+        .collect()
+    }
+
+    fn project(files: &[(&str, &str)]) -> ProjectAnalyzer {
+      let mut project = TestProject::new()
+        .add_file(
+          "client.ts",
+          "export class Client {}\nexport class Server {}\nexport default class Pool {}\nexport type Options = {};",
+        )
+        .entry("index.ts");
+      for (path, content) in files {
+        project = project.add_file(path, content);
+      }
+      project.build().0
+    }
+
+    #[test]
+    fn import_type_only() {
+      let analyzer = project(&[(
+        "index.ts",
+        "import type { Client } from './client';\nimport { type Server } from './client';",
+      )]);
+
+      assert_eq!(
+        type_only_used(&analyzer),
+        vec![
+          ("client.ts:Client".to_string(), vec![]),
+          ("client.ts:Server".to_string(), vec![]),
+        ]
+      );
+    }
+
+    #[test]
+    fn value_import_used_in_type_positions() {
+      let analyzer = project(&[
+        (
+          "index.ts",
+          "import Pool, { Client, Server } from './client';\nlet client: Client;\nclass Local implements Server {}\ninterface Props { pool: Pool }",
+        ),
+        (
+          "other.ts",
+          "import { Client } from './client';\nnew Client();",
+        ),
+      ]);
+
+      assert_eq!(
+        type_only_used(&analyzer),
+        vec![
+          ("client.ts:Server".to_string(), vec!["index.ts".to_string()]),
+          (
+            "client.ts:default".to_string(),
+            vec!["index.ts".to_string()]
+          ),
+        ]
+      );
+    }
+
+    #[test]
+    fn value_usage_is_not_reported() {
+      let analyzer = project(&[(
+        "index.ts",
+        "import { Client, Server } from './client';\nimport type { Options } from './client';\nconst c: Client = new Client();\nconsole.log(Server);",
+      )]);
+
+      assert_eq!(type_only_used(&analyzer), vec![]);
+    }
+
+    #[test]
+    fn through_barrel() {
+      let analyzer = project(&[
+        (
+          "index.ts",
+          "import { Client } from './barrel';\nexport function connect(client: Client) {}",
+        ),
+        ("barrel.ts", "export { Client } from './client';"),
+      ]);
+
+      assert_eq!(
+        type_only_used(&analyzer),
+        vec![
+          ("barrel.ts:Client".to_string(), vec!["index.ts".to_string()]),
+          ("client.ts:Client".to_string(), vec![]),
+        ]
+      );
+    }
+  }
+
+  // ===== Used In File =====
+  mod used_in_file {
+    use super::*;
+
+    fn used_in_file(utils: &str) -> Vec<(String, bool)> {
+      let project = TestProject::new()
+        .add_file("index.ts", "// no imports")
+        .add_file("utils.ts", utils)
+        .entry("index.ts");
+      let (analyzer, _) = project.build();
+
+      analyzer
+        .find_unused_exports()
+        .into_iter()
+        .map(|(_, export)| (export.name, export.used_in_file))
+        .collect()
+    }
+
+    #[test]
+    fn local_references() {
+      assert_eq!(
+        used_in_file(
+          "export const helper = () => 1;\nexport const dead = 2;\nexport type Config = { a: number };\nexport function run(c: Config) { return helper(); }",
+        ),
+        vec![
+          ("Config".to_string(), true),
+          ("dead".to_string(), false),
+          ("helper".to_string(), true),
+          ("run".to_string(), false),
+        ]
+      );
+    }
+
+    #[test]
+    fn export_statements_are_not_references() {
+      assert_eq!(
+        used_in_file("const a = 1;\nconst b = 2;\nexport { a, b as c };\nexport default a;"),
+        vec![
+          ("a".to_string(), false),
+          ("c".to_string(), false),
+          ("default".to_string(), false),
+        ]
+      );
+    }
+
+    #[test]
+    fn commonjs_export_values_are_not_references() {
+      let project = TestProject::new()
+        .add_file("index.js", "// no imports")
+        .add_file(
+          "utils.js",
+          "function a() {}\nfunction b() {}\nfunction c() { return b(); }\nmodule.exports = { a, b, c };",
+        )
+        .entry("index.js");
+      let (analyzer, _) = project.build();
+
+      let flags: Vec<_> = analyzer
+        .find_unused_exports()
+        .into_iter()
+        .map(|(_, export)| (export.name, export.used_in_file))
+        .collect();
+      assert_eq!(
+        flags,
+        vec![
+          ("a".to_string(), false),
+          ("b".to_string(), true),
+          ("c".to_string(), false),
+        ]
+      );
+    }
+
+    #[test]
+    fn shadowing_bindings_are_not_references() {
+      assert_eq!(
+        used_in_file(
+          "export const dead = 1;\nexport function other(dead: number) { return dead; }\nexport type T = 1;\nexport function f() { type T = 2; const x: T = 2; return x; }",
+        ),
+        vec![
+          ("T".to_string(), false),
+          ("dead".to_string(), false),
+          ("f".to_string(), false),
+          ("other".to_string(), false),
+        ]
+      );
+    }
+
+    #[test]
+    fn recursion_is_not_a_reference() {
+      assert_eq!(
+        used_in_file(
+          "export function rec(n: number): number { return n && rec(n - 1); }\nexport const fact = (n: number): number => (n ? n * fact(n - 1) : 1);\nexport class Node { next?: Node; }\nexport function run() { return rec(1); }",
+        ),
+        vec![
+          ("Node".to_string(), false),
+          ("fact".to_string(), false),
+          ("rec".to_string(), true),
+          ("run".to_string(), false),
+        ]
+      );
+    }
+
+    #[test]
+    fn vue_script_blocks() {
+      let project = TestProject::new()
+        .add_file("index.ts", "// no imports")
+        .add_file(
+          "Widget.vue",
+          "<template><div /></template>\n<script lang=\"ts\">\nexport const helper = () => 1;\nexport const dead = 2;\nexport function run(dead: number) { return helper() + dead; }\n</script>",
+        )
+        .entry("index.ts");
+      let (analyzer, _) = project.build();
+
+      let flags: Vec<_> = analyzer
+        .find_unused_exports()
+        .into_iter()
+        .map(|(_, export)| (export.name, export.used_in_file))
+        .collect();
+      assert_eq!(
+        flags,
+        vec![
+          ("dead".to_string(), false),
+          ("helper".to_string(), true),
+          ("run".to_string(), false),
+        ]
+      );
+    }
+
+    #[test]
+    fn reexports_are_never_used_in_file() {
+      assert_eq!(
+        used_in_file("import { x } from './x';\nexport { y } from './x';\nconsole.log(y);"),
+        vec![("y".to_string(), false)]
+      );
+    }
+  }
+
+  // ===== Default Exports =====
+  mod default_exports {
+    use super::*;
+
+    #[test]
+    fn default_export_unused() {
+      let project = TestProject::new()
+        .add_file("index.ts", "console.log('entry');")
+        .add_file("utils.ts", "export default function foo() {}")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts"]);
+      assert_unused(&analyzer, vec![("utils.ts", "default")]);
+    }
+
+    #[test]
+    fn default_export_used() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import foo from './utils';")
+        .add_file("utils.ts", "export default function foo() {}")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
+      assert_unused(&analyzer, vec![]);
+    }
+
+    #[test]
+    fn mixed_default_and_named() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import foo, { bar } from './utils';")
+        .add_file(
+          "utils.ts",
+          "export default function foo() {}\nexport const bar = 1;\nexport const baz = 2;",
+        )
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
+      assert_unused(&analyzer, vec![("utils.ts", "baz")]);
+    }
+  }
+
+  // ===== Namespace Imports =====
+  mod namespace {
+    use super::*;
+
+    #[test]
+    fn namespace_import_marks_all_used() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import * as utils from './utils';")
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
+      assert_unused(&analyzer, vec![]);
+    }
+
+    fn utils_project(index: &str) -> TestProject {
+      TestProject::new()
+        .add_file("index.ts", index)
+        .add_file(
+          "utils.ts",
+          "export const foo = 1;\nexport const bar = 2;\nexport type Baz = string;",
+        )
+        .entry("index.ts")
+    }
+
+    #[test]
+    fn member_access() {
+      let project =
+        utils_project("import * as utils from './utils';\nconsole.log(utils.foo, utils['bar']);");
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![("utils.ts", "Baz")]);
+    }
+
+    #[test]
+    fn destructuring() {
+      let project =
+        utils_project("import * as utils from './utils';\nconst { foo, bar: renamed } = utils;");
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![("utils.ts", "Baz")]);
+    }
+
+    #[test]
+    fn qualified_type_name() {
+      let project =
+        utils_project("import * as utils from './utils';\nlet baz: utils.Baz = String(utils.foo);");
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+    }
+
+    #[test]
+    fn escaping_namespace_marks_all_used() {
+      for index in [
+        "import * as utils from './utils';\nconsole.log(utils.foo);\nregister(utils);",
+        "import * as utils from './utils';\nconst copy = { ...utils };",
+        "import * as utils from './utils';\nfunction get() { return utils; }",
+        "import * as utils from './utils';\nconst key = 'foo';\nconsole.log(utils[key]);",
+        "import * as utils from './utils';\nconst { foo, ...rest } = utils;",
+      ] {
+        let (analyzer, _) = utils_project(index).build();
+        assert_unused(&analyzer, vec![]);
+      }
+    }
+
+    #[test]
+    fn member_access_through_barrel() {
+      let project = TestProject::new()
+        .add_file(
+          "index.ts",
+          "import * as utils from './barrel';\nutils.foo();",
+        )
+        .add_file("barrel.ts", "export * from './utils';")
+        .add_file(
+          "utils.ts",
+          "export function foo() {}\nexport function bar() {}",
+        )
+        .entry("index.ts");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+    }
+  }
+
+  // ===== Dynamic Imports =====
+  mod dynamic_imports {
+    use super::*;
+
+    fn page_project(index: &str) -> TestProject {
+      TestProject::new()
+        .add_file("index.ts", index)
+        .add_file(
+          "page.ts",
+          "export default function Page() {}\nexport const foo = 1;\nexport const bar = 2;",
+        )
+        .entry("index.ts")
+    }
+
+    #[test]
+    fn dynamic_import_is_an_edge() {
+      let project = page_project("export async function load() {\n  return import('./page');\n}");
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "page.ts"]);
+      assert_unused(&analyzer, vec![("index.ts", "load")]);
+    }
+
+    #[test]
+    fn template_literal_specifier() {
+      let project = page_project("const { foo } = await import(`./page`);");
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "page.ts"]);
+      assert_unused(&analyzer, vec![("page.ts", "bar"), ("page.ts", "default")]);
+    }
+
+    #[test]
+    fn computed_specifier_is_ignored() {
+      let project = page_project("const name = 'page';\nimport(`./${name}`);");
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts"]);
+    }
+
+    #[test]
+    fn then_callback_members() {
+      for index in [
+        "import('./page').then(m => m.foo);",
+        "import('./page').then(({ foo }) => foo);",
+        "import('./page').then(function (m) { return m['foo']; });",
+      ] {
+        let (analyzer, _) = page_project(index).build();
+        assert_unused(&analyzer, vec![("page.ts", "bar"), ("page.ts", "default")]);
+      }
+    }
+
+    #[test]
+    fn lazy_loads_default() {
+      for index in [
+        "const Page = React.lazy(() => import('./page'));",
+        "const Page = lazy(() => import('./page'));",
+        "const Page = defineAsyncComponent(() => import('./page'));",
+      ] {
+        let (analyzer, _) = page_project(index).build();
+        assert_unused(&analyzer, vec![("page.ts", "bar"), ("page.ts", "foo")]);
+      }
+    }
+
+    #[test]
+    fn unknown_usage_marks_all_used() {
+      for index in [
+        "const page = await import('./page');",
+        "import('./page').then(register);",
+        "import('./page').then(m => register(m));",
+      ] {
+        let (analyzer, _) = page_project(index).build();
+        assert_unused(&analyzer, vec![]);
+      }
+    }
+  }
+
+  // ===== Glob Imports =====
+  mod glob_imports {
+    use super::*;
+
+    fn pages_project(index: &str) -> TestProject {
+      TestProject::new()
+        .add_file("src/router.ts", index)
+        .add_file(
+          "src/pages/Home.vue",
+          "<script setup lang=\"ts\">\nexport const title = 'home';\n</script>",
+        )
+        .add_file(
+          "src/pages/admin/Users.ts",
+          "export default function Users() {}\nexport const meta = {};",
+        )
+        .add_file("src/pages/admin/helpers.js", "export const helper = 1;")
+        .add_file("src/other.ts", "export const other = 1;")
+        .entry("src/router.ts")
+    }
+
+    #[test]
+    fn vite_glob_lazy() {
+      let project = pages_project("const pages = import.meta.glob('./pages/**/*.ts');");
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["src/router.ts", "src/pages/admin/Users.ts"],
+      );
+      assert_unused(
+        &analyzer,
+        vec![
+          ("src/other.ts", "other"),
+          ("src/pages/Home.vue", "title"),
+          ("src/pages/admin/helpers.js", "helper"),
+        ],
+      );
+    }
+
+    #[test]
+    fn vite_glob_patterns_and_negation() {
+      let project = pages_project(
+        "const pages = import.meta.glob(['/src/pages/**/*', '!**/helpers.js'], { eager: true });",
+      );
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &[
+          "src/router.ts",
+          "src/pages/Home.vue",
+          "src/pages/admin/Users.ts",
+        ],
+      );
+      assert!(
+        !analyzer
+          .compute_reachable(entries)
+          .contains(Path::new("src/pages/admin/helpers.js"))
+      );
+    }
+
+    #[test]
+    fn vite_glob_unanchored_negation() {
+      let project = TestProject::new()
+        .add_file(
+          "src/pages/index.ts",
+          "const shared = import.meta.glob(['../shared/*.js', '!**/helpers.js']);",
+        )
+        .add_file("src/shared/format.js", "export const format = 1;")
+        .add_file("src/shared/helpers.js", "export const helper = 1;")
+        .entry("src/pages/index.ts");
+      let (analyzer, entries) = project.build();
+
+      let reachable = analyzer.compute_reachable(entries);
+      assert!(reachable.contains(Path::new("src/shared/format.js")));
+      assert!(!reachable.contains(Path::new("src/shared/helpers.js")));
+    }
+
+    #[test]
+    fn vite_glob_import_option() {
+      let project = pages_project(
+        "const pages = import.meta.glob('./pages/*/*.ts', { eager: true, import: 'default' });",
+      );
+      let (analyzer, _) = project.build();
+
+      assert_unused(
+        &analyzer,
+        vec![
+          ("src/other.ts", "other"),
+          ("src/pages/Home.vue", "title"),
+          ("src/pages/admin/Users.ts", "meta"),
+          ("src/pages/admin/helpers.js", "helper"),
+        ],
+      );
+    }
+
+    #[test]
+    fn vite_glob_through_alias() {
+      let mut aliases = HashMap::new();
+      aliases.insert("@".to_string(), "src".to_string());
+
+      let project = pages_project("const pages = import.meta.glob('@/pages/*.vue');");
+      let (analyzer, entries) = project.build_with_config(Some(SweepyConfig {
+        alias: aliases,
+        ..Default::default()
+      }));
+
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["src/router.ts", "src/pages/Home.vue"],
+      );
+    }
+
+    #[test]
+    fn vite_glob_through_tsconfig_paths() {
+      use crate::tsconfig::PathMapping;
+
+      let project =
+        pages_project("const pages = import.meta.glob(['~pages/**/*', '!~pages/admin/*.js']);");
+      let (analyzer, entries) = project.build_with_config(Some(SweepyConfig {
+        tsconfig: Some(TsconfigPaths {
+          paths: vec![PathMapping {
+            pattern: "~pages/*".to_string(),
+            targets: vec!["src/pages/*".to_string()],
+          }],
+          ..Default::default()
+        }),
+        ..Default::default()
+      }));
+
+      let reachable = analyzer.compute_reachable(entries);
+      assert!(reachable.contains(Path::new("src/pages/admin/Users.ts")));
+      assert!(!reachable.contains(Path::new("src/pages/admin/helpers.js")));
+    }
+
+    #[test]
+    fn require_context() {
+      let project =
+        pages_project("const pages = require.context('./pages', true, /\\.(ts|vue)$/);");
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &[
+          "src/router.ts",
+          "src/pages/Home.vue",
+          "src/pages/admin/Users.ts",
+        ],
+      );
+      assert_unused(
+        &analyzer,
+        vec![
+          ("src/other.ts", "other"),
+          ("src/pages/admin/helpers.js", "helper"),
+        ],
+      );
+    }
+
+    #[test]
+    fn require_context_not_recursive() {
+      let project = pages_project("const pages = require.context('./pages', false);");
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["src/router.ts", "src/pages/Home.vue"],
+      );
+      assert!(
+        !analyzer
+          .compute_reachable(entries)
+          .contains(Path::new("src/pages/admin/Users.ts"))
+      );
+    }
+  }
+
+  // ===== CommonJS =====
+  mod commonjs {
+    use super::*;
+
+    #[test]
+    fn require_is_an_edge() {
+      let project = TestProject::new()
+        .add_file("index.js", "require('./setup');")
+        .add_file("setup.js", "exports.run = function () {};")
+        .add_file("unused.js", "exports.other = 1;")
+        .entry("index.js");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.js", "setup.js"]);
+      assert_unused(&analyzer, vec![("unused.js", "other")]);
+    }
+
+    #[test]
+    fn destructured_require() {
+      let project = TestProject::new()
+        .add_file(
+          "index.cjs",
+          "const { foo } = require('./utils.cjs');\nfoo();",
+        )
+        .add_file(
+          "utils.cjs",
+          "function foo() {}\nfunction bar() {}\nmodule.exports = { foo, bar, baz: 1 };",
+        )
+        .entry("index.cjs");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.cjs", "utils.cjs"]);
+      assert_unused(&analyzer, vec![("utils.cjs", "bar"), ("utils.cjs", "baz")]);
+    }
+
+    #[test]
+    fn require_member_access() {
+      let project = TestProject::new()
+        .add_file("index.js", "require('./utils').foo();")
+        .add_file(
+          "utils.js",
+          "module.exports.foo = () => {};\nexports.bar = () => {};",
+        )
+        .entry("index.js");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![("utils.js", "bar")]);
+    }
+
+    #[test]
+    fn require_binding_marks_all_used() {
+      let project = TestProject::new()
+        .add_file("index.js", "const utils = require('./utils');")
+        .add_file("utils.js", "exports.foo = 1;\nexports.bar = 2;")
+        .entry("index.js");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![]);
+    }
+
+    #[test]
+    fn module_exports_value_is_default() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import format from './format';")
+        .add_file("format.js", "module.exports = function format() {};")
+        .add_file("unused.js", "class Unused {}\nmodule.exports = Unused;")
+        .entry("index.ts");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![("unused.js", "default")]);
+    }
+
+    #[test]
+    fn esm_imports_from_commonjs() {
+      let project = TestProject::new()
+        .add_file(
+          "index.ts",
+          "import { foo } from './utils';\nimport cfg from './config';",
+        )
+        .add_file("utils.js", "exports.foo = 1;\nexports.bar = 2;")
+        .add_file(
+          "config.js",
+          "module.exports = { port: 80, host: 'localhost' };",
+        )
+        .entry("index.ts");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![("utils.js", "bar")]);
+    }
+
+    #[test]
+    fn typescript_import_equals_and_export_assignment() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import config = require('./config');")
+        .add_file(
+          "config.ts",
+          "const config = { port: 80 };\nexport = config;",
+        )
+        .add_file("unused.ts", "export = 42;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "config.ts"]);
+      assert_unused(&analyzer, vec![("unused.ts", "default")]);
+    }
+  }
+
+  // ===== Re-exports =====
+  mod reexports {
+    use super::*;
+
+    #[test]
+    fn reexport_all_unused() {
+      let project = TestProject::new()
+        .add_file("index.ts", "console.log('entry');")
+        .add_file("barrel.ts", "export * from './utils';")
+        .add_file("utils.ts", "export const foo = 1;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts"]);
+      assert_unused(&analyzer, vec![("utils.ts", "foo")]);
+    }
+
+    #[test]
+    fn reexport_all_used() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo } from './barrel';")
+        .add_file("barrel.ts", "export * from './utils';")
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "barrel.ts", "utils.ts"]);
+      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+    }
+
+    #[test]
+    fn reexport_named() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo } from './barrel';")
+        .add_file("barrel.ts", "export { foo, bar } from './utils';")
+        .add_file(
+          "utils.ts",
+          "export const foo = 1;\nexport const bar = 2;\nexport const baz = 3;",
+        )
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "barrel.ts", "utils.ts"]);
+      assert_unused(
+        &analyzer,
+        vec![
+          ("barrel.ts", "bar"),
+          ("utils.ts", "bar"),
+          ("utils.ts", "baz"),
+        ],
+      );
+    }
+
+    #[test]
+    fn reexport_with_alias() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { myFoo } from './barrel';")
+        .add_file("barrel.ts", "export { foo as myFoo } from './utils';")
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "barrel.ts", "utils.ts"]);
+      // Importing 'myFoo' from the barrel uses 'foo' from utils through the alias
+      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+    }
+
+    #[test]
+    fn multi_hop_barrel_chain() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo } from './components';")
+        .add_file("components/index.ts", "export * from './forms';")
+        .add_file(
+          "components/forms/index.ts",
+          "export { foo, bar } from './inputs';",
+        )
+        .add_file("components/forms/inputs.ts", "export * from './text';")
+        .add_file(
+          "components/forms/text.ts",
+          "export const foo = 1;\nexport const bar = 2;",
+        )
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "components/forms/text.ts"],
+      );
+      assert_unused(
+        &analyzer,
+        vec![
+          ("components/forms/index.ts", "bar"),
+          ("components/forms/text.ts", "bar"),
+        ],
+      );
+    }
+
+    #[test]
+    fn renamed_reexport_chain() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { publicName } from './outer';")
+        .add_file(
+          "outer.ts",
+          "export { innerName as publicName } from './inner';",
+        )
+        .add_file("inner.ts", "export { foo as innerName } from './utils';")
+        .add_file(
+          "utils.ts",
+          "export const foo = 1;\nexport const innerName = 2;",
+        )
+        .entry("index.ts");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![("utils.ts", "innerName")]);
+    }
+
+    #[test]
+    fn reexport_default() {
+      let project = TestProject::new()
+        .add_file(
+          "index.ts",
+          "import Button, { Card } from './components';",
+        )
+        .add_file(
+          "components.ts",
+          "export { default } from './Button';\nexport { default as Card } from './Card';\nexport { default as Modal } from './Modal';",
+        )
+        .add_file("Button.ts", "export default function Button() {}")
+        .add_file("Card.ts", "export default class Card {}")
+        .add_file("Modal.ts", "export default {};")
+        .entry("index.ts");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(
+        &analyzer,
+        vec![("Modal.ts", "default"), ("components.ts", "Modal")],
+      );
+    }
+
+    #[test]
+    fn import_then_export() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { a, renamed } from './barrel';")
+        .add_file(
+          "barrel.ts",
+          "import { a } from './a';\nimport { b as bLocal } from './b';\nimport c from './c';\nexport { a, bLocal as renamed, c };",
+        )
+        .add_file("a.ts", "export const a = 1;\nexport const unusedA = 2;")
+        .add_file("b.ts", "export const b = 1;")
+        .add_file("c.ts", "export default 1;")
+        .entry("index.ts");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(
+        &analyzer,
+        vec![("a.ts", "unusedA"), ("barrel.ts", "c"), ("c.ts", "default")],
+      );
+    }
+
+    #[test]
+    fn import_then_export_used_locally() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import './barrel';")
+        .add_file(
+          "barrel.ts",
+          "import { a } from './a';\nconsole.log(a);\nexport { a };",
+        )
+        .add_file("a.ts", "export const a = 1;")
+        .entry("index.ts");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![("barrel.ts", "a")]);
+    }
+
+    #[test]
+    fn export_all_cycle() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo, missing } from './a';")
+        .add_file("a.ts", "export * from './b';")
+        .add_file(
+          "b.ts",
+          "export * from './a';\nexport const foo = 1;\nexport const bar = 2;",
+        )
+        .entry("index.ts");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![("b.ts", "bar")]);
+    }
+
+    #[test]
+    fn namespace_import_through_named_reexport() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import * as barrel from './barrel';")
+        .add_file("barrel.ts", "export { foo } from './utils';")
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .entry("index.ts");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+    }
+
+    #[test]
+    fn namespace_import_via_reexport() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import * as utils from './barrel';")
+        .add_file("barrel.ts", "export * from './utils';")
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "barrel.ts", "utils.ts"]);
+      assert_unused(&analyzer, vec![]);
+    }
+
+    #[test]
+    fn namespace_reexport_unused() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { other } from './barrel';")
+        .add_file(
+          "barrel.ts",
+          "export * as helpers from './helpers';\nexport const other = 1;",
+        )
+        .add_file("helpers.ts", "export const foo = 1;\nexport const bar = 2;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "barrel.ts", "helpers.ts"],
+      );
+      assert_unused(
+        &analyzer,
+        vec![
+          ("barrel.ts", "helpers"),
+          ("helpers.ts", "bar"),
+          ("helpers.ts", "foo"),
+        ],
+      );
+    }
+
+    #[test]
+    fn namespace_reexport_used() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { helpers } from './barrel';")
+        .add_file("barrel.ts", "export * as helpers from './helpers';")
+        .add_file(
+          "helpers.ts",
+          "export * from './format';\nexport const foo = 1;",
+        )
+        .add_file("format.ts", "export const format = 1;")
+        .entry("index.ts");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(&analyzer, vec![]);
+    }
+
+    #[test]
+    fn namespace_reexport_not_forwarded_by_export_all() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo } from './barrel';")
+        .add_file("barrel.ts", "export * from './inner';")
+        .add_file("inner.ts", "export * as helpers from './helpers';")
+        .add_file("helpers.ts", "export const foo = 1;")
+        .entry("index.ts");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(
+        &analyzer,
+        vec![("helpers.ts", "foo"), ("inner.ts", "helpers")],
+      );
+    }
+
+    #[test]
+    fn import_namespace_then_export() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { other } from './barrel';")
+        .add_file(
+          "barrel.ts",
+          "import * as helpers from './helpers';\nexport { helpers };\nexport const other = 1;",
+        )
+        .add_file("helpers.ts", "export const foo = 1;")
+        .entry("index.ts");
+
+      let (analyzer, _) = project.build();
+
+      assert_unused(
+        &analyzer,
+        vec![("barrel.ts", "helpers"), ("helpers.ts", "foo")],
+      );
+    }
+  }
+
+  // ===== Path Resolution =====
+  mod path_resolution {
+    use super::*;
+
+    #[test]
+    fn deep_relative_path() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import './deep/folder/module';")
+        .add_file(
+          "deep/folder/module.ts",
+          "import { foo } from '../../utils';",
+        )
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "deep/folder/module.ts", "utils.ts"],
+      );
+      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+    }
+
+    #[test]
+    fn import_with_js_extension() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo } from './utils.js';")
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
+      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+    }
+
+    #[test]
+    fn index_file_resolution() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo } from './utils/index';")
+        .add_file(
+          "utils/index.ts",
+          "export const foo = 1;\nexport const bar = 2;",
+        )
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "utils/index.ts"]);
+      assert_unused(&analyzer, vec![("utils/index.ts", "bar")]);
+    }
+
+    #[test]
+    fn candidate_matrix() {
+      let files = [
+        "src/user.service.ts",
+        "src/app.spec.ts",
+        "src/vite.config.mts",
+        "src/counter.store.js",
+        "src/Button.tsx",
+        "src/legacy.cts",
+        "src/both.ts",
+        "src/both.js",
+        "src/plain.js",
+        "src/env.d.ts",
+        "src/Card.vue",
+      ];
+      let file_set: HashSet<PathBuf> = files.iter().map(PathBuf::from).collect();
+      let config = SweepyConfig::default();
+
+      let cases = [
+        // multi-dot names get extensions appended
+        ("./user.service", Some("src/user.service.ts")),
+        ("./app.spec", Some("src/app.spec.ts")),
+        ("./vite.config", Some("src/vite.config.mts")),
+        ("./counter.store", Some("src/counter.store.js")),
+        // JS extensions map to TypeScript sources
+        ("./user.service.js", Some("src/user.service.ts")),
+        ("./Button.js", Some("src/Button.tsx")),
+        ("./Button.jsx", Some("src/Button.tsx")),
+        ("./vite.config.mjs", Some("src/vite.config.mts")),
+        ("./legacy.cjs", Some("src/legacy.cts")),
+        ("./both.js", Some("src/both.ts")),
+        // explicit extensions still match exactly
+        ("./plain.js", Some("src/plain.js")),
+        ("./both.ts", Some("src/both.ts")),
+        ("./Card.vue", Some("src/Card.vue")),
+        // declaration files
+        ("./env", Some("src/env.d.ts")),
+        ("./env.js", Some("src/env.d.ts")),
+        // no replacement of the last segment
+        ("./user", None),
+        ("./app", None),
+      ];
+
+      for (spec, expected) in cases {
+        assert_eq!(
+          resolve_relative_import_from_set(Path::new("src/index.ts"), spec, &file_set, &config),
+          expected.map(PathBuf::from),
+          "resolving {}",
+          spec
+        );
+      }
+    }
+
+    #[test]
+    fn multi_dot_filenames() {
+      let project = TestProject::new()
+        .add_file(
+          "index.ts",
+          "import { UserService } from './user.service';\nimport { config } from './app.config';",
+        )
+        .add_file(
+          "user.service.ts",
+          "export class UserService {}\nexport const unused = 1;",
+        )
+        .add_file("user.ts", "export const user = 1;")
+        .add_file("app.config.ts", "export const config = {};")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "user.service.ts", "app.config.ts"],
+      );
+      assert_unused(
+        &analyzer,
+        vec![("user.service.ts", "unused"), ("user.ts", "user")],
+      );
+    }
+
+    #[test]
+    fn directory_import_resolves_index() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo } from './utils';")
+        .add_file(
+          "utils/index.ts",
+          "export const foo = 1;\nexport const bar = 2;",
+        )
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "utils/index.ts"]);
+      assert_unused(&analyzer, vec![("utils/index.ts", "bar")]);
+    }
+
+    #[test]
+    fn directory_import_resolves_module_index() {
+      let project = TestProject::new()
+        .add_file(
+          "index.ts",
+          "import { foo } from './lib';\nimport { bar } from './cjs';",
+        )
+        .add_file("lib/index.mts", "export const foo = 1;")
+        .add_file("cjs/index.cjs", "export const bar = 1;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "lib/index.mts", "cjs/index.cjs"],
+      );
+      assert_unused(&analyzer, vec![]);
+    }
+
+    #[test]
+    fn file_takes_precedence_over_directory() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo } from './utils';")
+        .add_file("utils.ts", "export const foo = 1;")
+        .add_file("utils/index.ts", "export const foo = 1;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
+      assert_unused(&analyzer, vec![("utils/index.ts", "foo")]);
+    }
+
+    #[test]
+    fn directory_import_uses_package_json() {
+      let mut packages = HashMap::new();
+      packages.insert(
+        PathBuf::from("packages/ui"),
+        PackageJson {
+          types: Some("./dist/index.d.ts".to_string()),
+          main: Some("./src/main".to_string()),
+          ..Default::default()
+        },
+      );
+      let config = SweepyConfig {
+        packages,
+        ..Default::default()
+      };
+
+      let project = TestProject::new()
+        .add_file("index.ts", "import { Button } from './packages/ui';")
+        .add_file(
+          "packages/ui/src/main.ts",
+          "export const Button = 1;\nexport const Icon = 2;",
+        )
+        .add_file("packages/ui/index.ts", "export const Unrelated = 1;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build_with_config(Some(config));
+
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "packages/ui/src/main.ts"],
+      );
+      assert_unused(
+        &analyzer,
+        vec![
+          ("packages/ui/index.ts", "Unrelated"),
+          ("packages/ui/src/main.ts", "Icon"),
+        ],
+      );
+    }
+  }
+
+  // ===== Reachable Imports Only =====
+  mod reachable_imports_only {
+    use super::*;
+
+    fn assert_unused_from_reachable(
+      analyzer: &ProjectAnalyzer,
+      entries: &[PathBuf],
+      expected: Vec<(&str, &str)>,
+    ) {
+      let reachable = analyzer.compute_reachable(entries.to_vec());
+      let unused: Vec<_> = analyzer
+        .find_unused_exports_with(Some(&reachable))
+        .into_iter()
+        .map(|(file, export)| (file, export.name))
+        .collect();
+      let mut expected: Vec<_> = expected
+        .into_iter()
+        .map(|(file, name)| (PathBuf::from(file), name.to_string()))
+        .collect();
+      expected.sort();
+      assert_eq!(unused, expected);
+    }
+
+    #[test]
+    fn dead_importer_does_not_keep_export_alive() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo } from './utils';")
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .add_file("dead.ts", "import { bar } from './utils';")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_unused(&analyzer, vec![]);
+      assert_unused_from_reachable(&analyzer, &entries, vec![("utils.ts", "bar")]);
+    }
+
+    #[test]
+    fn dead_subgraph_reported_together() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo } from './utils';")
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .add_file(
+          "dead.ts",
+          "import { helper } from './dead-helper';\nexport const page = helper;",
+        )
+        .add_file(
+          "dead-helper.ts",
+          "import { bar } from './utils';\nimport { page } from './dead';\nexport const helper = bar;",
+        )
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_unused_from_reachable(
+        &analyzer,
+        &entries,
+        vec![
+          ("dead-helper.ts", "helper"),
+          ("dead.ts", "page"),
+          ("utils.ts", "bar"),
+        ],
+      );
+    }
+
+    #[test]
+    fn dead_barrel_does_not_keep_export_alive() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo } from './utils';")
+        .add_file("barrel.ts", "export * from './utils';")
+        .add_file("dead.ts", "import { bar } from './barrel';")
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_unused_from_reachable(&analyzer, &entries, vec![("utils.ts", "bar")]);
+    }
+  }
+
+  // ===== Unused Files =====
+  mod unused_files {
+    use super::*;
+
+    fn unused_files(analyzer: &ProjectAnalyzer, entries: &[PathBuf]) -> Vec<PathBuf> {
+      let reachable = analyzer.compute_reachable(entries.to_vec());
+      analyzer.find_unused_files(&reachable)
+    }
+
+    fn sample_project() -> TestProject {
+      TestProject::new()
+        .add_file("index.ts", "import { foo } from './utils';")
+        .add_file("utils.ts", "export const foo = 1;")
+        .add_file("dead.ts", "import { foo } from './utils';")
+        .add_file("vite.config.ts", "export default {};")
+        .add_file(".eslintrc.cjs", "module.exports = {};")
+        .add_file("env.d.ts", "declare const VERSION: string;")
+        .add_file("utils.spec.ts", "import { foo } from './utils';")
+        .add_file("__tests__/setup.ts", "export {};")
+        .entry("index.ts")
+    }
+
+    #[test]
+    fn reports_unreachable_files() {
+      let (analyzer, entries) = sample_project().build();
+
+      assert_eq!(
+        unused_files(&analyzer, &entries),
+        vec![
+          PathBuf::from(".eslintrc.cjs"),
+          PathBuf::from("__tests__/setup.ts"),
+          PathBuf::from("dead.ts"),
+          PathBuf::from("env.d.ts"),
+          PathBuf::from("utils.spec.ts"),
+          PathBuf::from("vite.config.ts"),
+        ]
+      );
+    }
+
+    #[test]
+    fn ignores_config_declaration_and_test_files() {
+      let config = SweepyConfig {
+        unused_files: UnusedFilesOptions {
+          ignore_config_files: true,
+          ignore_declaration_files: true,
+          ignore_test_files: true,
+        },
+        ..Default::default()
+      };
+
+      let (analyzer, entries) = sample_project().build_with_config(Some(config));
+
+      assert_eq!(
+        unused_files(&analyzer, &entries),
+        vec![PathBuf::from("dead.ts")]
+      );
+    }
+
+    #[test]
+    fn file_classification() {
+      assert!(is_declaration_file(Path::new("types/env.d.ts")));
+      assert!(is_declaration_file(Path::new("shims.d.mts")));
+      assert!(!is_declaration_file(Path::new("d.ts")));
+
+      assert!(is_config_file(Path::new("jest.config.js")));
+      assert!(is_config_file(Path::new(".prettierrc.mjs")));
+      assert!(!is_config_file(Path::new("src/config.ts")));
+
+      assert!(is_test_file(Path::new("src/user.service.spec.ts")));
+      assert!(is_test_file(Path::new("src/__mocks__/api.ts")));
+      assert!(!is_test_file(Path::new("src/test.ts")));
+    }
+  }
+
+  // ===== Side Effects =====
+  mod side_effects {
+    use super::*;
+
+    #[test]
+    fn side_effect_import_makes_file_reachable() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import './setup';")
+        .add_file(
+          "setup.ts",
+          "console.log('setup');\nexport const config = {};",
+        )
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "setup.ts"]);
+      assert_unused(&analyzer, vec![("setup.ts", "config")]);
+    }
+  }
+
+  // ===== Multi-entry =====
+  mod multi_entry {
+    use super::*;
+
+    #[test]
+    fn multiple_entrypoints() {
+      let project = TestProject::new()
+        .add_file("entry1.ts", "import { foo } from './utils';")
+        .add_file("entry2.ts", "import { bar } from './utils';")
+        .add_file(
+          "utils.ts",
+          "export const foo = 1;\nexport const bar = 2;\nexport const baz = 3;",
+        )
+        .entry("entry1.ts")
+        .entry("entry2.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["entry1.ts", "entry2.ts", "utils.ts"]);
+      assert_unused(&analyzer, vec![("utils.ts", "baz")]);
+    }
+  }
+
+  // ===== Import Aliases =====
+  mod aliases {
+    use super::*;
+
+    #[test]
+    fn import_with_alias() {
+      let project = TestProject::new()
+        .add_file("index.ts", "import { foo as myFoo } from './utils';")
+        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
+      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+    }
+
+    #[test]
+    fn path_alias_basic() {
+      let mut aliases = HashMap::new();
+      aliases.insert("@".to_string(), "src".to_string());
+      let config = SweepyConfig {
+        alias: aliases,
+        ..Default::default()
+      };
+
+      let project = TestProject::new()
+        .add_file("index.ts", "import { helper } from '@/utils';")
+        .add_file(
+          "src/utils.ts",
+          "export const helper = 1;\nexport const unused = 2;",
+        )
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build_with_config(Some(config));
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "src/utils.ts"]);
+      assert_unused(&analyzer, vec![("src/utils.ts", "unused")]);
+    }
+
+    #[test]
+    fn path_alias_nested() {
+      let mut aliases = HashMap::new();
+      aliases.insert("@".to_string(), "src".to_string());
+      let config = SweepyConfig {
+        alias: aliases,
+        ..Default::default()
+      };
+
+      let project = TestProject::new()
+        .add_file(
+          "index.ts",
+          "import { deepHelper } from '@/lib/helpers/utils';",
+        )
+        .add_file(
+          "src/lib/helpers/utils.ts",
+          "export const deepHelper = 1;\nexport const notUsed = 2;",
+        )
+        .entry("index.ts");
+
+      let (analyzer, entries) = project.build_with_config(Some(config));
+
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "src/lib/helpers/utils.ts"],
+      );
+      assert_unused(&analyzer, vec![("src/lib/helpers/utils.ts", "notUsed")]);
+    }
 
     #[test]
-    fn named_exports_all_unused() {
+    fn path_alias_multiple() {
+      let mut aliases = HashMap::new();
+      aliases.insert("@".to_string(), "src".to_string());
+      aliases.insert("~".to_string(), "lib".to_string());
+      let config = SweepyConfig {
+        alias: aliases,
+        ..Default::default()
+      };
+
       let project = TestProject::new()
-        .add_file("index.ts", "console.log('entry');")
         .add_file(
-          "utils.ts",
-          "export const foo = 1;\nexport function bar() {}",
+          "index.ts",
+          "import { foo } from '@/utils';\nimport { bar } from '~/helpers';",
+        )
+        .add_file(
+          "src/utils.ts",
+          "export const foo = 1;\nexport const unused1 = 2;",
+        )
+        .add_file(
+          "lib/helpers.ts",
+          "export const bar = 1;\nexport const unused2 = 2;",
         )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, entries) = project.build_with_config(Some(config));
 
-      assert_reachable(&analyzer, &entries, &["index.ts"]);
-      assert_unused(&analyzer, vec![("utils.ts", "bar"), ("utils.ts", "foo")]);
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "src/utils.ts", "lib/helpers.ts"],
+      );
+      assert_unused(
+        &analyzer,
+        vec![("src/utils.ts", "unused1"), ("lib/helpers.ts", "unused2")],
+      );
     }
 
     #[test]
-    fn named_exports_some_used() {
+    fn path_alias_with_reexport() {
+      let mut aliases = HashMap::new();
+      aliases.insert("@".to_string(), "src".to_string());
+      let config = SweepyConfig {
+        alias: aliases,
+        ..Default::default()
+      };
+
       let project = TestProject::new()
-        .add_file("index.ts", "import { foo } from './utils';")
-        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .add_file("index.ts", "import { foo } from '@/barrel';")
+        .add_file("src/barrel.ts", "export { foo } from './utils';")
+        .add_file(
+          "src/utils.ts",
+          "export const foo = 1;\nexport const bar = 2;",
+        )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, entries) = project.build_with_config(Some(config));
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
-      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "src/barrel.ts", "src/utils.ts"],
+      );
+      assert_unused(&analyzer, vec![("src/utils.ts", "bar")]);
     }
 
     #[test]
-    fn named_exports_all_used() {
+    fn path_alias_mixed_with_relative() {
+      let mut aliases = HashMap::new();
+      aliases.insert("@".to_string(), "src".to_string());
+      let config = SweepyConfig {
+        alias: aliases,
+        ..Default::default()
+      };
+
       let project = TestProject::new()
-        .add_file("index.ts", "import { foo, bar } from './utils';")
-        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .add_file("index.ts", "import { foo } from '@/utils';")
+        .add_file(
+          "src/utils.ts",
+          "export { foo } from './helpers';\nexport const localUnused = 2;",
+        )
+        .add_file(
+          "src/helpers.ts",
+          "export const foo = 1;\nexport const helperUnused = 2;",
+        )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, entries) = project.build_with_config(Some(config));
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
-      assert_unused(&analyzer, vec![]);
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "src/utils.ts", "src/helpers.ts"],
+      );
+      assert_unused(
+        &analyzer,
+        vec![
+          ("src/helpers.ts", "helperUnused"),
+          ("src/utils.ts", "localUnused"),
+        ],
+      );
     }
 
     #[test]
-    fn type_only_imports() {
+    fn path_alias_without_extension() {
+      let mut aliases = HashMap::new();
+      aliases.insert("@components".to_string(), "src/components".to_string());
+      let config = SweepyConfig {
+        alias: aliases,
+        ..Default::default()
+      };
+
       let project = TestProject::new()
+        .add_file("index.ts", "import { Button } from '@components/Button';")
         .add_file(
-          "index.ts",
-          "import type { MyType } from './types'; import { foo } from './types';",
-        )
-        .add_file(
-          "types.ts",
-          "export type MyType = string;\nexport const foo = 1;\nexport const bar = 2;",
+          "src/components/Button.tsx",
+          "export const Button = () => {};\nexport const Icon = () => {};",
         )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, entries) = project.build_with_config(Some(config));
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "types.ts"]);
-      assert_unused(&analyzer, vec![("types.ts", "bar")]);
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "src/components/Button.tsx"],
+      );
+      assert_unused(&analyzer, vec![("src/components/Button.tsx", "Icon")]);
     }
   }
 
-  // ===== Default Exports =====
-  mod default_exports {
+  // ===== tsconfig Paths =====
+  mod tsconfig_paths {
     use super::*;
+    use crate::tsconfig::PathMapping;
+
+    fn tsconfig_config(base_url: Option<&str>, paths: &[(&str, &[&str])]) -> SweepyConfig {
+      SweepyConfig {
+        tsconfig: Some(TsconfigPaths {
+          base_url: base_url.map(PathBuf::from),
+          paths: paths
+            .iter()
+            .map(|(pattern, targets)| PathMapping {
+              pattern: pattern.to_string(),
+              targets: targets.iter().map(|t| t.to_string()).collect(),
+            })
+            .collect(),
+          paths_base: PathBuf::from(base_url.unwrap_or("")),
+        }),
+        ..Default::default()
+      }
+    }
 
     #[test]
-    fn default_export_unused() {
+    fn wildcard_pattern() {
+      let config = tsconfig_config(None, &[("@/*", &["src/*"])]);
+
       let project = TestProject::new()
-        .add_file("index.ts", "console.log('entry');")
-        .add_file("utils.ts", "export default function foo() {}")
+        .add_file("index.ts", "import { helper } from '@/utils';")
+        .add_file(
+          "src/utils.ts",
+          "export const helper = 1;\nexport const unused = 2;",
+        )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, entries) = project.build_with_config(Some(config));
 
-      assert_reachable(&analyzer, &entries, &["index.ts"]);
-      assert_unused(&analyzer, vec![("utils.ts", "default")]);
+      assert_reachable(&analyzer, &entries, &["index.ts", "src/utils.ts"]);
+      assert_unused(&analyzer, vec![("src/utils.ts", "unused")]);
     }
 
     #[test]
-    fn default_export_used() {
+    fn fallback_targets() {
+      let config = tsconfig_config(None, &[("@/*", &["src/*", "generated/*"])]);
+
       let project = TestProject::new()
-        .add_file("index.ts", "import foo from './utils';")
-        .add_file("utils.ts", "export default function foo() {}")
+        .add_file(
+          "index.ts",
+          "import { a } from '@/local';\nimport { b } from '@/api';",
+        )
+        .add_file("src/local.ts", "export const a = 1;")
+        .add_file(
+          "generated/api.ts",
+          "export const b = 1;\nexport const c = 2;",
+        )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, entries) = project.build_with_config(Some(config));
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
-      assert_unused(&analyzer, vec![]);
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "src/local.ts", "generated/api.ts"],
+      );
+      assert_unused(&analyzer, vec![("generated/api.ts", "c")]);
     }
 
     #[test]
-    fn mixed_default_and_named() {
+    fn base_url_resolution() {
+      let config = tsconfig_config(Some("src"), &[]);
+
       let project = TestProject::new()
-        .add_file("index.ts", "import foo, { bar } from './utils';")
+        .add_file("index.ts", "import { helper } from 'lib/helpers';")
         .add_file(
-          "utils.ts",
-          "export default function foo() {}\nexport const bar = 1;\nexport const baz = 2;",
+          "src/lib/helpers.ts",
+          "export const helper = 1;\nexport const unused = 2;",
         )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, entries) = project.build_with_config(Some(config));
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
-      assert_unused(&analyzer, vec![("utils.ts", "baz")]);
+      assert_reachable(&analyzer, &entries, &["index.ts", "src/lib/helpers.ts"]);
+      assert_unused(&analyzer, vec![("src/lib/helpers.ts", "unused")]);
     }
   }
 
-  // ===== Namespace Imports =====
-  mod namespace {
+  // ===== Mixed Extensions =====
+  mod mixed_extensions {
     use super::*;
 
     #[test]
-    fn namespace_import_marks_all_used() {
+    fn js_and_ts_files() {
       let project = TestProject::new()
-        .add_file("index.ts", "import * as utils from './utils';")
-        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .add_file("index.ts", "import { foo } from './utils.js';")
+        .add_file("utils.js", "export const foo = 1;\nexport const bar = 2;")
         .entry("index.ts");
 
       let (analyzer, entries) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
-      assert_unused(&analyzer, vec![]);
+      assert_reachable(&analyzer, &entries, &["index.ts", "utils.js"]);
+      assert_unused(&analyzer, vec![("utils.js", "bar")]);
     }
-  }
-
-  // ===== Re-exports =====
-  mod reexports {
-    use super::*;
 
     #[test]
-    fn reexport_all_unused() {
+    fn jsx_file() {
       let project = TestProject::new()
-        .add_file("index.ts", "console.log('entry');")
-        .add_file("barrel.ts", "export * from './utils';")
-        .add_file("utils.ts", "export const foo = 1;")
-        .entry("index.ts");
+        .add_file("index.tsx", "import { Component } from './component.jsx';")
+        .add_file(
+          "component.jsx",
+          "export const Component = () => {};\nexport const Other = () => {};",
+        )
+        .entry("index.tsx");
 
       let (analyzer, entries) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts"]);
-      assert_unused(&analyzer, vec![("utils.ts", "foo")]);
+      assert_reachable(&analyzer, &entries, &["index.tsx", "component.jsx"]);
+      assert_unused(&analyzer, vec![("component.jsx", "Other")]);
     }
+  }
+
+  // ===== Circular Dependencies =====
+  mod circular {
+    use super::*;
 
     #[test]
-    fn reexport_all_used() {
+    fn circular_imports() {
       let project = TestProject::new()
-        .add_file("index.ts", "import { foo } from './barrel';")
-        .add_file("barrel.ts", "export * from './utils';")
-        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .add_file("index.ts", "import { foo } from './a';")
+        .add_file("a.ts", "import { bar } from './b';\nexport const foo = 1;")
+        .add_file("b.ts", "import { foo } from './a';\nexport const bar = 2;")
         .entry("index.ts");
 
       let (analyzer, entries) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "barrel.ts", "utils.ts"]);
-      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+      assert_reachable(&analyzer, &entries, &["index.ts", "a.ts", "b.ts"]);
+      assert_unused(&analyzer, vec![]);
+    }
+
+    /// Each cycle as its kind and `file -> specifier` hops
+    fn cycles(analyzer: &ProjectAnalyzer) -> Vec<(bool, Vec<(String, String)>)> {
+      analyzer
+        .find_cycles()
+        .into_iter()
+        .map(|cycle| {
+          let hops = cycle
+            .path
+            .into_iter()
+            .map(|(file, edge)| (file.to_string_lossy().to_string(), edge.specifier))
+            .collect();
+          (cycle.type_only, hops)
+        })
+        .collect()
+    }
+
+    fn hops(path: &[(&str, &str)]) -> Vec<(String, String)> {
+      path
+        .iter()
+        .map(|(file, spec)| (file.to_string(), spec.to_string()))
+        .collect()
     }
 
     #[test]
-    fn reexport_named() {
+    fn reports_runtime_cycle_path() {
       let project = TestProject::new()
-        .add_file("index.ts", "import { foo } from './barrel';")
-        .add_file("barrel.ts", "export { foo, bar } from './utils';")
+        .add_file("index.ts", "import { foo } from './a';")
         .add_file(
-          "utils.ts",
-          "export const foo = 1;\nexport const bar = 2;\nexport const baz = 3;",
+          "a.ts",
+          "import { bar } from './b';\nexport const foo = bar;",
+        )
+        .add_file("b.ts", "export { baz as bar } from './c';")
+        .add_file(
+          "c.ts",
+          "import { foo } from './a';\nexport const baz = () => foo;",
         )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, _) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "barrel.ts", "utils.ts"]);
-      assert_unused(
-        &analyzer,
-        vec![
-          ("barrel.ts", "bar"),
-          ("utils.ts", "bar"),
-          ("utils.ts", "baz"),
-        ],
+      assert_eq!(
+        cycles(&analyzer),
+        vec![(
+          false,
+          hops(&[("a.ts", "./b"), ("b.ts", "./c"), ("c.ts", "./a")])
+        )]
       );
     }
 
     #[test]
-    fn reexport_with_alias() {
+    fn type_only_cycles_are_separate() {
       let project = TestProject::new()
-        .add_file("index.ts", "import { myFoo } from './barrel';")
-        .add_file("barrel.ts", "export { foo as myFoo } from './utils';")
-        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
-        .entry("index.ts");
+        .add_file(
+          "user.ts",
+          "import type { Post } from './post';\nexport interface User { posts: Post[] }",
+        )
+        .add_file(
+          "post.ts",
+          "import { User } from './user';\nexport interface Post { author: User }",
+        )
+        .add_file(
+          "self.ts",
+          "import * as self from './self';\nexport const a = self;",
+        )
+        .entry("user.ts");
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, _) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "barrel.ts", "utils.ts"]);
-      // The alias export creates a new export name, so 'foo' from utils is not directly used
-      // Only 'bar' remains unused since 'myFoo' is imported (but myFoo doesn't exist in utils)
-      assert_unused(&analyzer, vec![("utils.ts", "bar"), ("utils.ts", "foo")]);
+      assert_eq!(
+        cycles(&analyzer),
+        vec![
+          (false, hops(&[("self.ts", "./self")])),
+          (true, hops(&[("post.ts", "./user"), ("user.ts", "./post")])),
+        ]
+      );
     }
 
     #[test]
-    fn namespace_import_via_reexport() {
+    fn type_cycle_goes_through_a_type_edge() {
+      // a <-> b at runtime, and c closes a larger loop with a type-only import
       let project = TestProject::new()
-        .add_file("index.ts", "import * as utils from './barrel';")
-        .add_file("barrel.ts", "export * from './utils';")
-        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
-        .entry("index.ts");
+        .add_file("a.ts", "import { b } from './b';\nexport const a = b;")
+        .add_file(
+          "b.ts",
+          "import { a } from './a';\nimport { c } from './c';\nexport const b = () => a + c;",
+        )
+        .add_file("c.ts", "import type { A } from './a';\nexport const c = 1;")
+        .entry("a.ts");
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, _) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "barrel.ts", "utils.ts"]);
-      assert_unused(&analyzer, vec![]);
+      let found = analyzer.find_cycles();
+      assert_eq!(found.len(), 2);
+      assert_eq!(
+        found[0].files,
+        vec![PathBuf::from("a.ts"), PathBuf::from("b.ts")]
+      );
+      assert_eq!(
+        cycles(&analyzer)[1],
+        (
+          true,
+          hops(&[("c.ts", "./a"), ("a.ts", "./b"), ("b.ts", "./c")])
+        )
+      );
     }
   }
 
-  // ===== Path Resolution =====
-  mod path_resolution {
+  // ===== Vue Files =====
+  mod vue_files {
     use super::*;
 
     #[test]
-    fn deep_relative_path() {
+    fn vue_file_with_script_setup() {
       let project = TestProject::new()
-        .add_file("index.ts", "import './deep/folder/module';")
+        .add_file("index.ts", "import { useCounter } from './Counter.vue';")
         .add_file(
-          "deep/folder/module.ts",
-          "import { foo } from '../../utils';",
+          "Counter.vue",
+          r#"<template>
+  <div>{{ count }}</div>
+</template>
+<script setup>
+export const useCounter = () => {
+  const count = 1;
+  return { count };
+};
+export const unusedHelper = () => {};
+</script>"#,
         )
-        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
         .entry("index.ts");
 
       let (analyzer, entries) = project.build();
 
-      assert_reachable(
-        &analyzer,
-        &entries,
-        &["index.ts", "deep/folder/module.ts", "utils.ts"],
-      );
-      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+      assert_reachable(&analyzer, &entries, &["index.ts", "Counter.vue"]);
+      assert_unused(&analyzer, vec![("Counter.vue", "unusedHelper")]);
     }
 
     #[test]
-    fn import_with_js_extension() {
+    fn vue_file_with_regular_script() {
       let project = TestProject::new()
-        .add_file("index.ts", "import { foo } from './utils.js';")
-        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
+        .add_file("index.ts", "import { helper } from './utils.vue';")
+        .add_file(
+          "utils.vue",
+          r#"<template>
+  <div>Utils</div>
+</template>
+<script>
+export const helper = () => 'help';
+export const unused = () => 'unused';
+</script>"#,
+        )
         .entry("index.ts");
 
       let (analyzer, entries) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
-      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+      assert_reachable(&analyzer, &entries, &["index.ts", "utils.vue"]);
+      assert_unused(&analyzer, vec![("utils.vue", "unused")]);
     }
 
     #[test]
-    fn index_file_resolution() {
+    fn vue_file_unused() {
       let project = TestProject::new()
-        .add_file("index.ts", "import { foo } from './utils/index';")
+        .add_file("index.ts", "console.log('entry');")
         .add_file(
-          "utils/index.ts",
-          "export const foo = 1;\nexport const bar = 2;",
+          "Component.vue",
+          r#"<template>
+  <div>Component</div>
+</template>
+<script setup>
+export const foo = 1;
+</script>"#,
         )
         .entry("index.ts");
 
       let (analyzer, entries) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "utils/index.ts"]);
-      assert_unused(&analyzer, vec![("utils/index.ts", "bar")]);
+      assert_reachable(&analyzer, &entries, &["index.ts"]);
+      assert_unused(&analyzer, vec![("Component.vue", "foo")]);
     }
-  }
-
-  // ===== Side Effects =====
-  mod side_effects {
-    use super::*;
 
     #[test]
-    fn side_effect_import_makes_file_reachable() {
+    fn vue_file_importing_another_vue() {
       let project = TestProject::new()
-        .add_file("index.ts", "import './setup';")
+        .add_file("index.ts", "import { App } from './App.vue';")
         .add_file(
-          "setup.ts",
-          "console.log('setup');\nexport const config = {};",
+          "App.vue",
+          r#"<template>
+  <Child />
+</template>
+<script setup>
+import { Child } from './Child.vue';
+export const App = {};
+</script>"#,
+        )
+        .add_file(
+          "Child.vue",
+          r#"<template>
+  <div>Child</div>
+</template>
+<script setup>
+export const Child = {};
+export const unused = 123;
+</script>"#,
         )
         .entry("index.ts");
 
       let (analyzer, entries) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "setup.ts"]);
-      assert_unused(&analyzer, vec![("setup.ts", "config")]);
+      assert_reachable(&analyzer, &entries, &["index.ts", "App.vue", "Child.vue"]);
+      assert_unused(&analyzer, vec![("Child.vue", "unused")]);
     }
-  }
-
-  // ===== Multi-entry =====
-  mod multi_entry {
-    use super::*;
 
     #[test]
-    fn multiple_entrypoints() {
+    fn vue_file_with_typescript() {
       let project = TestProject::new()
-        .add_file("entry1.ts", "import { foo } from './utils';")
-        .add_file("entry2.ts", "import { bar } from './utils';")
+        .add_file("index.ts", "import type { MyType } from './types.vue';")
         .add_file(
-          "utils.ts",
-          "export const foo = 1;\nexport const bar = 2;\nexport const baz = 3;",
+          "types.vue",
+          r#"<template>
+  <div>Types</div>
+</template>
+<script setup lang="ts">
+export type MyType = string;
+export type Interface = {
+  id: number;
+};
+export const value = 42;
+</script>"#,
         )
-        .entry("entry1.ts")
-        .entry("entry2.ts");
-
-      let (analyzer, entries) = project.build();
-
-      assert_reachable(&analyzer, &entries, &["entry1.ts", "entry2.ts", "utils.ts"]);
-      assert_unused(&analyzer, vec![("utils.ts", "baz")]);
-    }
-  }
-
-  // ===== Import Aliases =====
-  mod aliases {
-    use super::*;
-
-    #[test]
-    fn import_with_alias() {
-      let project = TestProject::new()
-        .add_file("index.ts", "import { foo as myFoo } from './utils';")
-        .add_file("utils.ts", "export const foo = 1;\nexport const bar = 2;")
         .entry("index.ts");
 
       let (analyzer, entries) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "utils.ts"]);
-      assert_unused(&analyzer, vec![("utils.ts", "bar")]);
+      assert_reachable(&analyzer, &entries, &["index.ts", "types.vue"]);
+      assert_unused(
+        &analyzer,
+        vec![("types.vue", "value"), ("types.vue", "Interface")],
+      );
     }
 
     #[test]
-    fn path_alias_basic() {
-      let mut aliases = HashMap::new();
-      aliases.insert("@".to_string(), "src".to_string());
-      let config = SweepyConfig { alias: aliases };
-
+    fn vue_imports_from_typescript() {
       let project = TestProject::new()
-        .add_file("index.ts", "import { helper } from '@/utils';")
+        .add_file("index.ts", "import { Component } from './Component.vue';")
         .add_file(
-          "src/utils.ts",
-          "export const helper = 1;\nexport const unused = 2;",
+          "Component.vue",
+          r#"<template>
+  <div>{{ helper() }}</div>
+</template>
+<script setup>
+import { helper } from './utils.ts';
+export const Component = {};
+</script>"#,
+        )
+        .add_file(
+          "utils.ts",
+          "export const helper = () => 'help';\nexport const unusedHelper = () => 'unused';",
         )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build_with_config(Some(config));
+      let (analyzer, entries) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "src/utils.ts"]);
-      assert_unused(&analyzer, vec![("src/utils.ts", "unused")]);
+      assert_reachable(
+        &analyzer,
+        &entries,
+        &["index.ts", "Component.vue", "utils.ts"],
+      );
+      assert_unused(&analyzer, vec![("utils.ts", "unusedHelper")]);
     }
 
     #[test]
-    fn path_alias_nested() {
-      let mut aliases = HashMap::new();
-      aliases.insert("@".to_string(), "src".to_string());
-      let config = SweepyConfig { alias: aliases };
-
+    fn typescript_imports_from_vue() {
       let project = TestProject::new()
+        .add_file("index.ts", "import { useStore } from './store.ts';")
         .add_file(
-          "index.ts",
-          "import { deepHelper } from '@/lib/helpers/utils';",
+          "store.ts",
+          "import { computed } from './composables.vue';\nexport const useStore = () => computed();\nexport const unused = 123;",
         )
         .add_file(
-          "src/lib/helpers/utils.ts",
-          "export const deepHelper = 1;\nexport const notUsed = 2;",
+          "composables.vue",
+          r#"<template>
+  <div>Composables</div>
+</template>
+<script setup>
+export const computed = () => ({ value: 42 });
+export const unusedComposable = () => {};
+</script>"#,
         )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build_with_config(Some(config));
+      let (analyzer, entries) = project.build();
 
       assert_reachable(
         &analyzer,
         &entries,
-        &["index.ts", "src/lib/helpers/utils.ts"],
+        &["index.ts", "store.ts", "composables.vue"],
+      );
+      assert_unused(
+        &analyzer,
+        vec![
+          ("store.ts", "unused"),
+          ("composables.vue", "unusedComposable"),
+        ],
       );
-      assert_unused(&analyzer, vec![("src/lib/helpers/utils.ts", "notUsed")]);
     }
 
     #[test]
-    fn path_alias_multiple() {
-      let mut aliases = HashMap::new();
-      aliases.insert("@".to_string(), "src".to_string());
-      aliases.insert("~".to_string(), "lib".to_string());
-      let config = SweepyConfig { alias: aliases };
-
+    fn mixed_vue_ts_imports_chain() {
       let project = TestProject::new()
+        .add_file("index.ts", "import { App } from './App.vue';")
         .add_file(
-          "index.ts",
-          "import { foo } from '@/utils';\nimport { bar } from '~/helpers';",
+          "App.vue",
+          r#"<template>
+  <div>App</div>
+</template>
+<script setup>
+import { api } from './api.ts';
+export const App = {};
+</script>"#,
         )
         .add_file(
-          "src/utils.ts",
-          "export const foo = 1;\nexport const unused1 = 2;",
+          "api.ts",
+          "import { http } from './http.vue';\nexport const api = { http };\nexport const unusedApi = {};",
         )
         .add_file(
-          "lib/helpers.ts",
-          "export const bar = 1;\nexport const unused2 = 2;",
+          "http.vue",
+          r#"<template>
+  <div>HTTP</div>
+</template>
+<script setup>
+export const http = { get: () => {} };
+export const unused = 'test';
+</script>"#,
         )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build_with_config(Some(config));
+      let (analyzer, entries) = project.build();
 
       assert_reachable(
         &analyzer,
         &entries,
-        &["index.ts", "src/utils.ts", "lib/helpers.ts"],
+        &["index.ts", "App.vue", "api.ts", "http.vue"],
       );
       assert_unused(
         &analyzer,
-        vec![("src/utils.ts", "unused1"), ("lib/helpers.ts", "unused2")],
+        vec![("api.ts", "unusedApi"), ("http.vue", "unused")],
       );
     }
 
     #[test]
-    fn path_alias_with_reexport() {
-      let mut aliases = HashMap::new();
-      aliases.insert("@".to_string(), "src".to_string());
-      let config = SweepyConfig { alias: aliases };
-
+    fn vue_imports_without_extension() {
       let project = TestProject::new()
-        .add_file("index.ts", "import { foo } from '@/barrel';")
-        .add_file("src/barrel.ts", "export { foo } from './utils';")
+        .add_file("index.ts", "import { Component } from './Component.vue';")
         .add_file(
-          "src/utils.ts",
-          "export const foo = 1;\nexport const bar = 2;",
+          "Component.vue",
+          r#"<template>
+  <div>Component</div>
+</template>
+<script setup>
+import { helper } from './utils';
+export const Component = {};
+</script>"#,
+        )
+        .add_file(
+          "utils.ts",
+          "export const helper = () => 'help';\nexport const unused = 'test';",
         )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build_with_config(Some(config));
+      let (analyzer, entries) = project.build();
 
       assert_reachable(
         &analyzer,
         &entries,
-        &["index.ts", "src/barrel.ts", "src/utils.ts"],
+        &["index.ts", "Component.vue", "utils.ts"],
       );
-      assert_unused(&analyzer, vec![("src/utils.ts", "bar")]);
+      assert_unused(&analyzer, vec![("utils.ts", "unused")]);
     }
 
     #[test]
-    fn path_alias_mixed_with_relative() {
-      let mut aliases = HashMap::new();
-      aliases.insert("@".to_string(), "src".to_string());
-      let config = SweepyConfig { alias: aliases };
-
+    fn vue_imports_from_vue_without_extension() {
       let project = TestProject::new()
-        .add_file("index.ts", "import { foo } from '@/utils';")
+        .add_file("index.ts", "import { App } from './App.vue';")
         .add_file(
-          "src/utils.ts",
-          "export { foo } from './helpers';\nexport const localUnused = 2;",
+          "App.vue",
+          r#"<template>
+  <Child />
+</template>
+<script setup>
+import { Child } from './Child';
+export const App = {};
+</script>"#,
         )
         .add_file(
-          "src/helpers.ts",
-          "export const foo = 1;\nexport const helperUnused = 2;",
+          "Child.vue",
+          r#"<template>
+  <div>Child</div>
+</template>
+<script setup>
+export const Child = {};
+export const unused = 123;
+</script>"#,
         )
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build_with_config(Some(config));
+      let (analyzer, entries) = project.build();
+
+      assert_reachable(&analyzer, &entries, &["index.ts", "App.vue", "Child.vue"]);
+      assert_unused(&analyzer, vec![("Child.vue", "unused")]);
+    }
+  }
+
+  // ===== Public API =====
+  mod public_api {
+    use super::*;
+
+    fn library() -> TestProject {
+      TestProject::new()
+        .add_file(
+          "index.ts",
+          "export * from './components';\nexport { format as formatDate } from './utils';\nexport const version = '1.0';\nexport default version;",
+        )
+        .add_file("components/index.ts", "export * from './Button';")
+        .add_file(
+          "components/Button.ts",
+          "export class Button {}\nexport type ButtonProps = {};\nexport default Button;",
+        )
+        .add_file(
+          "utils.ts",
+          "export function format() {}\nexport function internal() {}",
+        )
+        .entry("index.ts")
+    }
+
+    fn public_config() -> SweepyConfig {
+      SweepyConfig {
+        public_entries: vec![PathBuf::from("index.ts")],
+        ..Default::default()
+      }
+    }
+
+    #[test]
+    fn entry_exports_unused_by_default() {
+      let (analyzer, _) = library().build();
 
-      assert_reachable(
-        &analyzer,
-        &entries,
-        &["index.ts", "src/utils.ts", "src/helpers.ts"],
-      );
       assert_unused(
         &analyzer,
         vec![
-          ("src/helpers.ts", "helperUnused"),
-          ("src/utils.ts", "localUnused"),
+          ("components/Button.ts", "Button"),
+          ("components/Button.ts", "ButtonProps"),
+          ("components/Button.ts", "default"),
+          ("index.ts", "default"),
+          ("index.ts", "formatDate"),
+          ("index.ts", "version"),
+          ("utils.ts", "format"),
+          ("utils.ts", "internal"),
         ],
       );
     }
 
     #[test]
-    fn path_alias_without_extension() {
-      let mut aliases = HashMap::new();
-      aliases.insert("@components".to_string(), "src/components".to_string());
-      let config = SweepyConfig { alias: aliases };
-
-      let project = TestProject::new()
-        .add_file("index.ts", "import { Button } from '@components/Button';")
-        .add_file(
-          "src/components/Button.tsx",
-          "export const Button = () => {};\nexport const Icon = () => {};",
-        )
-        .entry("index.ts");
-
-      let (analyzer, entries) = project.build_with_config(Some(config));
+    fn entry_exports_used_through_barrels() {
+      let (analyzer, _) = library().build_with_config(Some(public_config()));
 
-      assert_reachable(
+      // `export *` does not forward Button's default
+      assert_unused(
         &analyzer,
-        &entries,
-        &["index.ts", "src/components/Button.tsx"],
+        vec![
+          ("components/Button.ts", "default"),
+          ("utils.ts", "internal"),
+        ],
       );
-      assert_unused(&analyzer, vec![("src/components/Button.tsx", "Icon")]);
     }
-  }
-
-  // ===== Mixed Extensions =====
-  mod mixed_extensions {
-    use super::*;
 
     #[test]
-    fn js_and_ts_files() {
-      let project = TestProject::new()
-        .add_file("index.ts", "import { foo } from './utils.js';")
-        .add_file("utils.js", "export const foo = 1;\nexport const bar = 2;")
-        .entry("index.ts");
-
-      let (analyzer, entries) = project.build();
+    fn public_api_lists_declarations() {
+      let (analyzer, _) = library().build_with_config(Some(public_config()));
+
+      let api: Vec<(String, String, String)> = analyzer
+        .find_public_api()
+        .into_iter()
+        .map(|(entry, name, file, export)| {
+          assert_eq!(entry, PathBuf::from("index.ts"));
+          (name, file.to_string_lossy().to_string(), export.name)
+        })
+        .collect();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "utils.js"]);
-      assert_unused(&analyzer, vec![("utils.js", "bar")]);
+      let expected = [
+        ("Button", "components/Button.ts", "Button"),
+        ("ButtonProps", "components/Button.ts", "ButtonProps"),
+        ("default", "index.ts", "default"),
+        ("formatDate", "utils.ts", "format"),
+        ("version", "index.ts", "version"),
+      ];
+      let expected: Vec<(String, String, String)> = expected
+        .iter()
+        .map(|(n, f, e)| (n.to_string(), f.to_string(), e.to_string()))
+        .collect();
+      assert_eq!(api, expected);
     }
 
     #[test]
-    fn jsx_file() {
+    fn public_api_stops_at_external_reexports() {
       let project = TestProject::new()
-        .add_file("index.tsx", "import { Component } from './component.jsx';")
-        .add_file(
-          "component.jsx",
-          "export const Component = () => {};\nexport const Other = () => {};",
-        )
-        .entry("index.tsx");
-
-      let (analyzer, entries) = project.build();
+        .add_file("index.ts", "export { ref } from 'vue';")
+        .entry("index.ts");
+      let (analyzer, _) = project.build_with_config(Some(public_config()));
 
-      assert_reachable(&analyzer, &entries, &["index.tsx", "component.jsx"]);
-      assert_unused(&analyzer, vec![("component.jsx", "Other")]);
+      let api = analyzer.find_public_api();
+      assert_eq!(api.len(), 1);
+      assert_eq!(api[0].2, PathBuf::from("index.ts"));
+      assert_eq!(api[0].3.kind, ExportKind::ReExport);
     }
   }
 
-  // ===== Circular Dependencies =====
-  mod circular {
+  // ===== Dependencies =====
+  mod dependencies {
     use super::*;
 
-    #[test]
-    fn circular_imports() {
-      let project = TestProject::new()
-        .add_file("index.ts", "import { foo } from './a';")
-        .add_file("a.ts", "import { bar } from './b';\nexport const foo = 1;")
-        .add_file("b.ts", "import { foo } from './a';\nexport const bar = 2;")
-        .entry("index.ts");
+    fn with_root_package(package: PackageJson) -> Option<SweepyConfig> {
+      let mut packages = HashMap::new();
+      packages.insert(PathBuf::new(), package);
+      Some(SweepyConfig {
+        packages,
+        ..Default::default()
+      })
+    }
 
-      let (analyzer, entries) = project.build();
+    fn unused_dependencies(
+      analyzer: &ProjectAnalyzer,
+      entries: &[PathBuf],
+    ) -> Vec<(String, &'static str)> {
+      let reachable = analyzer.compute_reachable(entries.to_vec());
+      analyzer
+        .find_unused_dependencies(&reachable)
+        .into_iter()
+        .map(|(name, kind)| (name, kind.as_str()))
+        .collect()
+    }
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "a.ts", "b.ts"]);
-      assert_unused(&analyzer, vec![]);
+    fn deps(names: &[&str]) -> Vec<String> {
+      names.iter().map(|n| n.to_string()).collect()
     }
-  }
 
-  // ===== Vue Files =====
-  mod vue_files {
-    use super::*;
+    #[test]
+    fn package_names_are_normalized() {
+      let config = SweepyConfig::default();
+
+      assert_eq!(package_name("lodash", &config).as_deref(), Some("lodash"));
+      assert_eq!(
+        package_name("lodash/fp", &config).as_deref(),
+        Some("lodash")
+      );
+      assert_eq!(
+        package_name("@vue/shared/dist/shared.js", &config).as_deref(),
+        Some("@vue/shared")
+      );
+      assert_eq!(package_name("@vue", &config), None);
+      assert_eq!(package_name("@/components/Button", &config), None);
+      assert_eq!(package_name("~/utils", &config), None);
+      assert_eq!(package_name("#internal", &config), None);
+      assert_eq!(package_name("node:fs", &config), None);
+      assert_eq!(package_name("fs/promises", &config), None);
+      assert_eq!(package_name("virtual:pwa-register", &config), None);
+      assert_eq!(package_name("./lodash", &config), None);
+    }
 
     #[test]
-    fn vue_file_with_script_setup() {
+    fn reports_dependencies_not_imported() {
+      let config = with_root_package(PackageJson {
+        dependencies: deps(&["lodash", "@vue/shared", "axios"]),
+        dev_dependencies: deps(&["vitest"]),
+        peer_dependencies: deps(&["vue"]),
+        ..Default::default()
+      });
+
       let project = TestProject::new()
-        .add_file("index.ts", "import { useCounter } from './Counter.vue';")
         .add_file(
-          "Counter.vue",
-          r#"<template>
-  <div>{{ count }}</div>
-</template>
-<script setup>
-export const useCounter = () => {
-  const count = 1;
-  return { count };
-};
-export const unusedHelper = () => {};
-</script>"#,
+          "index.ts",
+          "import fp from 'lodash/fp';\nimport { isArray } from '@vue/shared';\nimport { readFile } from 'node:fs';\nexport { ref } from 'vue';",
         )
         .entry("index.ts");
+      let (analyzer, entries) = project.build_with_config(config);
 
-      let (analyzer, entries) = project.build();
+      assert_eq!(
+        unused_dependencies(&analyzer, &entries),
+        vec![
+          ("axios".to_string(), "dependencies"),
+          ("vitest".to_string(), "devDependencies"),
+        ]
+      );
+    }
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "Counter.vue"]);
-      assert_unused(&analyzer, vec![("Counter.vue", "unusedHelper")]);
+    #[test]
+    fn imports_from_unreachable_files_do_not_count() {
+      let config = with_root_package(PackageJson {
+        dependencies: deps(&["lodash", "dayjs"]),
+        ..Default::default()
+      });
+
+      let project = TestProject::new()
+        .add_file("index.ts", "const lodash = require('lodash');")
+        .add_file("dead.ts", "import dayjs from 'dayjs';")
+        .entry("index.ts");
+      let (analyzer, entries) = project.build_with_config(config);
+
+      assert_eq!(
+        unused_dependencies(&analyzer, &entries),
+        vec![("dayjs".to_string(), "dependencies")]
+      );
     }
 
     #[test]
-    fn vue_file_with_regular_script() {
+    fn types_packages_follow_their_package() {
+      let config = with_root_package(PackageJson {
+        dependencies: deps(&["lodash", "@babel/core", "dayjs"]),
+        dev_dependencies: deps(&[
+          "@types/lodash",
+          "@types/babel__core",
+          "@types/dayjs",
+          "@types/node",
+        ]),
+        ..Default::default()
+      });
+
       let project = TestProject::new()
-        .add_file("index.ts", "import { helper } from './utils.vue';")
         .add_file(
-          "utils.vue",
-          r#"<template>
-  <div>Utils</div>
-</template>
-<script>
-export const helper = () => 'help';
-export const unused = () => 'unused';
-</script>"#,
+          "index.ts",
+          "import type { Dictionary } from 'lodash';\nimport { transform } from '@babel/core';",
         )
         .entry("index.ts");
+      let (analyzer, entries) = project.build_with_config(config);
 
-      let (analyzer, entries) = project.build();
-
-      assert_reachable(&analyzer, &entries, &["index.ts", "utils.vue"]);
-      assert_unused(&analyzer, vec![("utils.vue", "unused")]);
+      assert_eq!(
+        unused_dependencies(&analyzer, &entries),
+        vec![
+          ("@types/dayjs".to_string(), "devDependencies"),
+          ("dayjs".to_string(), "dependencies"),
+        ]
+      );
     }
 
     #[test]
-    fn vue_file_unused() {
+    fn resolved_aliases_are_not_packages() {
+      let mut alias = HashMap::new();
+      alias.insert("utils".to_string(), "src/utils".to_string());
+      let mut packages = HashMap::new();
+      packages.insert(
+        PathBuf::new(),
+        PackageJson {
+          dependencies: deps(&["utils"]),
+          ..Default::default()
+        },
+      );
+      let config = SweepyConfig {
+        alias,
+        packages,
+        ..Default::default()
+      };
+
       let project = TestProject::new()
-        .add_file("index.ts", "console.log('entry');")
-        .add_file(
-          "Component.vue",
-          r#"<template>
-  <div>Component</div>
-</template>
-<script setup>
-export const foo = 1;
-</script>"#,
-        )
+        .add_file("index.ts", "import { foo } from 'utils/foo';")
+        .add_file("src/utils/foo.ts", "export const foo = 1;")
         .entry("index.ts");
+      let (analyzer, entries) = project.build_with_config(Some(config));
 
-      let (analyzer, entries) = project.build();
+      assert_eq!(
+        unused_dependencies(&analyzer, &entries),
+        vec![("utils".to_string(), "dependencies")]
+      );
+    }
 
-      assert_reachable(&analyzer, &entries, &["index.ts"]);
-      assert_unused(&analyzer, vec![("Component.vue", "foo")]);
+    fn dependency_imports(
+      found: Vec<(String, PathBuf, Vec<PathBuf>)>,
+    ) -> Vec<(String, String, Vec<String>)> {
+      found
+        .into_iter()
+        .map(|(name, dir, importers)| {
+          (
+            name,
+            dir.to_string_lossy().to_string(),
+            importers
+              .iter()
+              .map(|p| p.to_string_lossy().to_string())
+              .collect(),
+          )
+        })
+        .collect()
     }
 
     #[test]
-    fn vue_file_importing_another_vue() {
+    fn unlisted_uses_nearest_package_json() {
+      let mut packages = HashMap::new();
+      packages.insert(
+        PathBuf::new(),
+        PackageJson {
+          name: Some("app".to_string()),
+          dependencies: deps(&["vue"]),
+          dev_dependencies: deps(&["@types/lodash"]),
+          ..Default::default()
+        },
+      );
+      packages.insert(
+        PathBuf::from("packages/ui"),
+        PackageJson {
+          dependencies: deps(&["dayjs"]),
+          ..Default::default()
+        },
+      );
+      let config = SweepyConfig {
+        packages,
+        ..Default::default()
+      };
+
       let project = TestProject::new()
-        .add_file("index.ts", "import { App } from './App.vue';")
         .add_file(
-          "App.vue",
-          r#"<template>
-  <Child />
-</template>
-<script setup>
-import { Child } from './Child.vue';
-export const App = {};
-</script>"#,
+          "index.ts",
+          "import { ref } from 'vue';\nimport axios from 'axios';\nimport type { Dictionary } from 'lodash';\nimport { helper } from 'app/helper';\nimport './packages/ui/index';",
         )
         .add_file(
-          "Child.vue",
-          r#"<template>
-  <div>Child</div>
-</template>
-<script setup>
-export const Child = {};
-export const unused = 123;
-</script>"#,
+          "packages/ui/index.ts",
+          "import dayjs from 'dayjs';\nimport { ref } from 'vue';\nimport axios from 'axios';",
+        )
+        .entry("index.ts");
+      let (analyzer, entries) = project.build_with_config(Some(config));
+      let reachable = analyzer.compute_reachable(entries);
+
+      assert_eq!(
+        dependency_imports(analyzer.find_unlisted_dependencies(&reachable)),
+        vec![
+          (
+            "axios".to_string(),
+            "".to_string(),
+            vec!["index.ts".to_string()]
+          ),
+          (
+            "axios".to_string(),
+            "packages/ui".to_string(),
+            vec!["packages/ui/index.ts".to_string()]
+          ),
+          (
+            "vue".to_string(),
+            "packages/ui".to_string(),
+            vec!["packages/ui/index.ts".to_string()]
+          ),
+        ]
+      );
+    }
+
+    #[test]
+    fn misplaced_dev_dependencies_in_production_files() {
+      let config = with_root_package(PackageJson {
+        dependencies: deps(&["vue"]),
+        dev_dependencies: deps(&["lodash", "vitest", "zod", "vite", "vue"]),
+        ..Default::default()
+      });
+
+      let project = TestProject::new()
+        .add_file(
+          "index.ts",
+          "import { ref } from 'vue';\nimport debounce from 'lodash/debounce';\nimport type { ZodType } from 'zod';\nimport './utils.test';\nimport './vite.config';",
         )
+        .add_file("utils.test.ts", "import { test } from 'vitest';\nimport { z } from 'zod';")
+        .add_file("vite.config.ts", "import { defineConfig } from 'vite';")
         .entry("index.ts");
+      let (analyzer, entries) = project.build_with_config(config);
+      let reachable = analyzer.compute_reachable(entries);
+
+      assert_eq!(
+        dependency_imports(analyzer.find_misplaced_dependencies(&reachable)),
+        vec![(
+          "lodash".to_string(),
+          "".to_string(),
+          vec!["index.ts".to_string()]
+        )]
+      );
+      assert_eq!(analyzer.find_unlisted_dependencies(&reachable), vec![]);
+    }
+  }
 
-      let (analyzer, entries) = project.build();
+  // ===== Unresolved Imports =====
+  mod unresolved_imports {
+    use super::*;
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "App.vue", "Child.vue"]);
-      assert_unused(&analyzer, vec![("Child.vue", "unused")]);
+    fn unresolved(analyzer: &ProjectAnalyzer) -> Vec<(String, String, &'static str, (u32, u32))> {
+      analyzer
+        .find_unresolved_imports()
+        .into_iter()
+        .map(|(file, spec, reason, position)| {
+          (
+            file.to_string_lossy().to_string(),
+            spec,
+            reason.as_str(),
+            position,
+          )
+        })
+        .collect()
     }
 
     #[test]
-    fn vue_file_with_typescript() {
+    fn reports_reasons_and_positions() {
       let project = TestProject::new()
-        .add_file("index.ts", "import type { MyType } from './types.vue';")
         .add_file(
-          "types.vue",
-          r#"<template>
-  <div>Types</div>
-</template>
-<script setup lang="ts">
-export type MyType = string;
-export type Interface = {
-  id: number;
-};
-export const value = 42;
-</script>"#,
+          "index.ts",
+          "import { a } from './missing';\nimport { b } from './button';\n  export * from './Card.tsx';\nconst c = require('./utils.js');",
         )
+        .add_file("Button.ts", "export const b = 1;")
+        .add_file("Card.vue", "<script setup lang=\"ts\"></script>")
+        .add_file("utils.ts", "export const c = 1;")
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, _) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "types.vue"]);
-      assert_unused(
-        &analyzer,
-        vec![("types.vue", "value"), ("types.vue", "Interface")],
+      assert_eq!(
+        unresolved(&analyzer),
+        vec![
+          (
+            "index.ts".to_string(),
+            "./missing".to_string(),
+            "no-matching-file",
+            (1, 19)
+          ),
+          (
+            "index.ts".to_string(),
+            "./button".to_string(),
+            "case-mismatch",
+            (2, 19)
+          ),
+          (
+            "index.ts".to_string(),
+            "./Card.tsx".to_string(),
+            "extension-mismatch",
+            (3, 17)
+          ),
+        ]
       );
     }
 
     #[test]
-    fn vue_imports_from_typescript() {
+    fn reports_missing_alias_targets() {
+      let mut alias = HashMap::new();
+      alias.insert("@".to_string(), "src".to_string());
+      let config = SweepyConfig {
+        alias,
+        ..Default::default()
+      };
+
       let project = TestProject::new()
-        .add_file("index.ts", "import { Component } from './Component.vue';")
         .add_file(
-          "Component.vue",
-          r#"<template>
-  <div>{{ helper() }}</div>
-</template>
-<script setup>
-import { helper } from './utils.ts';
-export const Component = {};
-</script>"#,
+          "src/index.ts",
+          "import { a } from '@/missing';\nimport { ref } from 'vue';\nimport { isArray } from '@vue/shared';",
         )
+        .entry("src/index.ts");
+
+      let (analyzer, _) = project.build_with_config(Some(config));
+
+      assert_eq!(
+        unresolved(&analyzer),
+        vec![(
+          "src/index.ts".to_string(),
+          "@/missing".to_string(),
+          "alias-target-missing",
+          (1, 19)
+        )]
+      );
+    }
+
+    #[test]
+    fn ignores_assets_and_queries() {
+      let project = TestProject::new()
         .add_file(
-          "utils.ts",
-          "export const helper = () => 'help';\nexport const unusedHelper = () => 'unused';",
+          "index.ts",
+          "import './style.css';\nimport logo from './logo.SVG';\nimport Worker from './worker?worker';\nimport raw from './missing.ts?raw';",
         )
+        .add_file("worker.ts", "self.onmessage = () => {};")
         .entry("index.ts");
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, _) = project.build();
 
-      assert_reachable(
-        &analyzer,
-        &entries,
-        &["index.ts", "Component.vue", "utils.ts"],
+      assert_eq!(
+        unresolved(&analyzer),
+        vec![(
+          "index.ts".to_string(),
+          "./missing.ts?raw".to_string(),
+          "no-matching-file",
+          (4, 17)
+        )]
       );
-      assert_unused(&analyzer, vec![("utils.ts", "unusedHelper")]);
     }
+  }
 
-    #[test]
-    fn typescript_imports_from_vue() {
-      let project = TestProject::new()
-        .add_file("index.ts", "import { useStore } from './store.ts';")
-        .add_file(
-          "store.ts",
-          "import { computed } from './composables.vue';\nexport const useStore = () => computed();\nexport const unused = 123;",
-        )
+  // ===== Import Paths =====
+  mod import_paths {
+    use super::*;
+
+    fn project() -> (ProjectAnalyzer, Vec<PathBuf>) {
+      TestProject::new()
         .add_file(
-          "composables.vue",
-          r#"<template>
-  <div>Composables</div>
-</template>
-<script setup>
-export const computed = () => ({ value: 42 });
-export const unusedComposable = () => {};
-</script>"#,
+          "index.ts",
+          "import { a } from './a';\nimport { b } from './b';\nconst page = () => import('./page');",
         )
-        .entry("index.ts");
+        .add_file("a.ts", "export { c as a } from './c';")
+        .add_file("b.ts", "import { helper } from './helper';\nexport const b = helper;")
+        .add_file("helper.ts", "import type { C } from './c';\nexport const helper = 1;")
+        .add_file("page.ts", "const c = require('./c');")
+        .add_file("c.ts", "export const c = 1;\nexport type C = number;")
+        .add_file("dead.ts", "import { c } from './c';")
+        .entry("index.ts")
+        .build()
+    }
 
+    fn paths(
+      analyzer: &ProjectAnalyzer,
+      entries: &[PathBuf],
+      target: &str,
+      limit: usize,
+    ) -> Vec<Vec<String>> {
+      analyzer
+        .find_import_paths(entries.to_vec(), Path::new(target), limit)
+        .into_iter()
+        .map(|files| {
+          files
+            .iter()
+            .map(|f| f.to_string_lossy().to_string())
+            .collect()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn shortest_path_first() {
+      let (analyzer, entries) = project();
+
+      assert_eq!(
+        paths(&analyzer, &entries, "c.ts", 1),
+        vec![vec!["index.ts", "a.ts", "c.ts"]]
+      );
+      assert_eq!(
+        paths(&analyzer, &entries, "c.ts", 10),
+        vec![
+          vec!["index.ts", "a.ts", "c.ts"],
+          vec!["index.ts", "page.ts", "c.ts"],
+          vec!["index.ts", "b.ts", "helper.ts", "c.ts"],
+        ]
+      );
+    }
+
+    #[test]
+    fn dense_graph() {
+      // 7 layers of 8 files, each importing every file of the next layer: 8^7 paths
+      let layer = |depth: usize| -> Vec<String> {
+        (0..8)
+          .map(|i| format!("l{}_{}", depth, i))
+          .collect::<Vec<_>>()
+      };
+      let imports = |names: Vec<String>| -> String {
+        names
+          .iter()
+          .map(|name| format!("import './{}';\n", name))
+          .collect()
+      };
+
+      let mut project = TestProject::new()
+        .add_file("index.ts", &imports(layer(0)))
+        .add_file("target.ts", "export {};")
+        .entry("index.ts");
+      for depth in 0..7 {
+        let next = if depth == 6 {
+          vec!["target".to_string()]
+        } else {
+          layer(depth + 1)
+        };
+        for name in layer(depth) {
+          project = project.add_file(&format!("{}.ts", name), &imports(next.clone()));
+        }
+      }
       let (analyzer, entries) = project.build();
 
-      assert_reachable(
-        &analyzer,
-        &entries,
-        &["index.ts", "store.ts", "composables.vue"],
+      let found = paths(&analyzer, &entries, "target.ts", 1);
+      assert_eq!(
+        found,
+        vec![vec![
+          "index.ts",
+          "l0_0.ts",
+          "l1_0.ts",
+          "l2_0.ts",
+          "l3_0.ts",
+          "l4_0.ts",
+          "l5_0.ts",
+          "l6_0.ts",
+          "target.ts"
+        ]]
       );
-      assert_unused(
-        &analyzer,
+
+      let found = paths(&analyzer, &entries, "target.ts", 20);
+      assert_eq!(found.len(), 20);
+      assert!(found.iter().all(|path| path.len() == 9));
+      let mut unique = found.clone();
+      unique.sort();
+      unique.dedup();
+      assert_eq!(unique.len(), 20);
+      assert_eq!(found[1][7], "l6_1.ts");
+    }
+
+    #[test]
+    fn paths_through_other_entrypoints() {
+      let (analyzer, _) = TestProject::new()
+        .add_file("a.ts", "import './shared';")
+        .add_file("b.ts", "import './a';\nimport './c';")
+        .add_file("c.ts", "import './shared';")
+        .add_file("shared.ts", "export {};")
+        .build();
+      let entries = vec![PathBuf::from("a.ts"), PathBuf::from("b.ts")];
+
+      assert_eq!(
+        paths(&analyzer, &entries, "shared.ts", 5),
         vec![
-          ("store.ts", "unused"),
-          ("composables.vue", "unusedComposable"),
-        ],
+          vec!["a.ts", "shared.ts"],
+          vec!["b.ts", "a.ts", "shared.ts"],
+          vec!["b.ts", "c.ts", "shared.ts"],
+        ]
+      );
+      assert_eq!(
+        paths(&analyzer, &entries, "a.ts", 5),
+        vec![vec!["a.ts"], vec!["b.ts", "a.ts"]]
       );
     }
 
     #[test]
-    fn mixed_vue_ts_imports_chain() {
-      let project = TestProject::new()
-        .add_file("index.ts", "import { App } from './App.vue';")
+    fn runtime_import_keeps_the_edge() {
+      let (analyzer, _) = TestProject::new()
         .add_file(
-          "App.vue",
-          r#"<template>
-  <div>App</div>
-</template>
-<script setup>
-import { api } from './api.ts';
-export const App = {};
-</script>"#,
+          "index.ts",
+          "import type { B } from './b';\nconst lazy = () => import('./b.ts');\nrequire('./b');",
+        )
+        .add_file("b.ts", "export type B = 1;")
+        .entry("index.ts")
+        .build();
+
+      let imports: Vec<_> = analyzer.edges[Path::new("index.ts")][Path::new("b.ts")]
+        .iter()
+        .map(|edge| (edge.specifier.as_str(), edge.kind.as_str(), edge.type_only))
+        .collect();
+      assert_eq!(
+        imports,
+        vec![
+          ("./b", "import", true),
+          ("./b.ts", "dynamic-import", false),
+          ("./b", "require", false),
+        ]
+      );
+
+      let edge = analyzer
+        .edge(Path::new("index.ts"), Path::new("b.ts"))
+        .unwrap();
+      assert_eq!(
+        (edge.specifier.as_str(), edge.kind.as_str(), edge.type_only),
+        ("./b.ts", "dynamic-import", false)
+      );
+    }
+
+    #[test]
+    fn edges_carry_specifier_and_kind() {
+      let (analyzer, _) = project();
+      let edge = |from: &str, to: &str| {
+        let edge = analyzer.edge(Path::new(from), Path::new(to)).unwrap();
+        (edge.specifier.as_str(), edge.kind.as_str(), edge.type_only)
+      };
+
+      assert_eq!(edge("index.ts", "a.ts"), ("./a", "import", false));
+      assert_eq!(
+        edge("index.ts", "page.ts"),
+        ("./page", "dynamic-import", false)
+      );
+      assert_eq!(edge("a.ts", "c.ts"), ("./c", "re-export", false));
+      assert_eq!(edge("page.ts", "c.ts"), ("./c", "require", false));
+      assert_eq!(edge("helper.ts", "c.ts"), ("./c", "import", true));
+    }
+
+    #[test]
+    fn unreachable_and_entry_files() {
+      let (analyzer, entries) = project();
+
+      assert!(paths(&analyzer, &entries, "dead.ts", 5).is_empty());
+      assert_eq!(
+        paths(&analyzer, &entries, "index.ts", 5),
+        vec![vec!["index.ts"]]
+      );
+    }
+  }
+
+  // ===== Affected Files =====
+  mod affected {
+    use super::*;
+
+    fn affected(changed: &[&str]) -> (Vec<String>, Vec<String>) {
+      let (analyzer, _) = TestProject::new()
+        .add_file(
+          "app.ts",
+          "import { a } from './a';\nimport { shared } from './shared';\nimport config from './config.json';\nconst pages = import.meta.glob('./pages/*.ts');",
         )
+        .add_file("admin.ts", "import { b } from './b';")
+        .add_file("worker.ts", "const job = () => import('./job');")
         .add_file(
-          "api.ts",
-          "import { http } from './http.vue';\nexport const api = { http };\nexport const unusedApi = {};",
+          "a.ts",
+          "export { shared as a } from './shared';\nexport * from './legacy';",
         )
         .add_file(
-          "http.vue",
-          r#"<template>
-  <div>HTTP</div>
-</template>
-<script setup>
-export const http = { get: () => {} };
-export const unused = 'test';
-</script>"#,
+          "b.ts",
+          "import type { Shared } from './shared';\nimport { gone } from './removed';\nimport './theme.css?inline';\nexport const b = 1;",
         )
-        .entry("index.ts");
+        .add_file("job.ts", "export const job = 1;")
+        .add_file(
+          "shared.ts",
+          "export const shared = 1;\nexport type Shared = number;",
+        )
+        .build();
+      let entries: Vec<PathBuf> = ["app.ts", "admin.ts", "worker.ts"]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+      let changed: Vec<PathBuf> = changed.iter().map(PathBuf::from).collect();
+
+      let (files, entries) = analyzer.find_affected(&entries, &changed);
+      let names = |paths: Vec<PathBuf>| -> Vec<String> {
+        paths
+          .iter()
+          .map(|p| p.to_string_lossy().to_string())
+          .collect()
+      };
+      (names(files), names(entries))
+    }
 
-      let (analyzer, entries) = project.build();
+    #[test]
+    fn transitive_importers_and_entries() {
+      assert_eq!(
+        affected(&["shared.ts"]),
+        (
+          vec!["a.ts", "admin.ts", "app.ts", "b.ts", "shared.ts"]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+          vec!["admin.ts".to_string(), "app.ts".to_string()],
+        )
+      );
+    }
 
-      assert_reachable(
-        &analyzer,
-        &entries,
-        &["index.ts", "App.vue", "api.ts", "http.vue"],
+    #[test]
+    fn dynamic_imports_and_changed_entries() {
+      assert_eq!(
+        affected(&["job.ts", "./admin.ts"]),
+        (
+          vec!["admin.ts", "job.ts", "worker.ts"]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+          vec!["admin.ts".to_string(), "worker.ts".to_string()],
+        )
       );
-      assert_unused(
-        &analyzer,
-        vec![("api.ts", "unusedApi"), ("http.vue", "unused")],
+    }
+
+    #[test]
+    fn deleted_files_and_assets() {
+      let strings =
+        |names: &[&str]| -> Vec<String> { names.iter().map(|n| n.to_string()).collect() };
+
+      assert_eq!(
+        affected(&["removed.ts"]),
+        (strings(&["admin.ts", "b.ts"]), strings(&["admin.ts"]))
+      );
+      assert_eq!(
+        affected(&["removed/index.tsx"]),
+        (strings(&["admin.ts", "b.ts"]), strings(&["admin.ts"]))
+      );
+      assert_eq!(
+        affected(&["theme.css"]),
+        (strings(&["admin.ts", "b.ts"]), strings(&["admin.ts"]))
+      );
+      assert_eq!(
+        affected(&["legacy.js"]),
+        (strings(&["a.ts", "app.ts"]), strings(&["app.ts"]))
+      );
+      assert_eq!(
+        affected(&["config.json"]),
+        (strings(&["app.ts"]), strings(&["app.ts"]))
+      );
+      assert_eq!(
+        affected(&["pages/deleted.ts"]),
+        (strings(&["app.ts"]), strings(&["app.ts"]))
       );
     }
 
     #[test]
-    fn vue_imports_without_extension() {
+    fn project_files_follow_real_resolution() {
+      let (analyzer, _) = TestProject::new()
+        .add_file("app.ts", "import { format } from './utils';")
+        .add_file("utils.ts", "export const format = 1;")
+        .add_file("utils/index.ts", "export const format = 2;")
+        .build();
+      let entries = vec![PathBuf::from("app.ts")];
+
+      assert_eq!(
+        analyzer.find_affected(&entries, &[PathBuf::from("utils/index.ts")]),
+        (vec![PathBuf::from("utils/index.ts")], vec![])
+      );
+      assert_eq!(
+        analyzer.find_affected(&entries, &[PathBuf::from("utils.ts")]),
+        (
+          vec![PathBuf::from("app.ts"), PathBuf::from("utils.ts")],
+          vec![PathBuf::from("app.ts")]
+        )
+      );
+    }
+
+    #[test]
+    fn unrelated_files_affect_nothing() {
+      assert_eq!(affected(&["README.md", "pages/notes.md"]), (vec![], vec![]));
+    }
+  }
+
+  // ===== References =====
+  mod references {
+    use super::*;
+
+    /// File, position, kind, name and whether it is type-only
+    type Found = (String, (u32, u32), &'static str, String, bool);
+
+    fn references(analyzer: &ProjectAnalyzer, file: &str, name: &str) -> Vec<Found> {
+      analyzer
+        .find_references(Path::new(file), name)
+        .into_iter()
+        .map(|r| {
+          (
+            r.file.to_string_lossy().to_string(),
+            (r.line, r.column),
+            r.kind.as_str(),
+            r.name,
+            r.type_only,
+          )
+        })
+        .collect()
+    }
+
+    fn reference(
+      file: &str,
+      position: (u32, u32),
+      kind: &'static str,
+      name: &str,
+      type_only: bool,
+    ) -> Found {
+      (
+        file.to_string(),
+        position,
+        kind,
+        name.to_string(),
+        type_only,
+      )
+    }
+
+    #[test]
+    fn follows_barrels_and_namespaces() {
       let project = TestProject::new()
-        .add_file("index.ts", "import { Component } from './Component.vue';")
         .add_file(
-          "Component.vue",
-          r#"<template>
-  <div>Component</div>
-</template>
-<script setup>
-import { helper } from './utils';
-export const Component = {};
-</script>"#,
+          "utils.ts",
+          "export function format() {}\nexport function parse() {}",
         )
+        .add_file("barrel.ts", "export * from './utils';")
+        .add_file("renamed.ts", "export { format as fmt } from './barrel';")
+        .add_file("grouped.ts", "export * as helpers from './utils';")
+        .add_file("a.ts", "import { format } from './utils';\nformat();")
+        .add_file("b.ts", "import * as lib from './barrel';\nlib.format();")
+        .add_file("c.ts", "import { fmt } from './renamed';\nfmt();")
+        .add_file("d.ts", "import * as lib from './barrel';\nlib.parse();")
         .add_file(
-          "utils.ts",
-          "export const helper = () => 'help';\nexport const unused = 'test';",
+          "e.ts",
+          "import { helpers } from './grouped';\nhelpers.format();",
         )
-        .entry("index.ts");
+        .add_file(
+          "f.ts",
+          "import { parse } from './utils';\nimport type { format } from './barrel';",
+        );
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, _) = project.build();
 
-      assert_reachable(
-        &analyzer,
-        &entries,
-        &["index.ts", "Component.vue", "utils.ts"],
+      assert_eq!(
+        references(&analyzer, "utils.ts", "format"),
+        vec![
+          reference("a.ts", (1, 24), "named", "format", false),
+          reference("b.ts", (1, 22), "namespace-member", "format", false),
+          reference("barrel.ts", (1, 15), "re-export", "format", false),
+          reference("c.ts", (1, 21), "named", "fmt", false),
+          reference("e.ts", (1, 25), "named", "helpers", false),
+          reference("f.ts", (2, 29), "named", "format", true),
+          reference("grouped.ts", (1, 13), "re-export", "format", false),
+          reference("renamed.ts", (1, 20), "re-export", "format", false),
+        ]
       );
-      assert_unused(&analyzer, vec![("utils.ts", "unused")]);
     }
 
     #[test]
-    fn vue_imports_from_vue_without_extension() {
+    fn own_exports_shadow_export_all() {
       let project = TestProject::new()
-        .add_file("index.ts", "import { App } from './App.vue';")
+        .add_file("utils.ts", "export const value = 1;\nexport default value;")
         .add_file(
-          "App.vue",
-          r#"<template>
-  <Child />
-</template>
-<script setup>
-import { Child } from './Child';
-export const App = {};
-</script>"#,
+          "barrel.ts",
+          "export * from './utils';\nexport const value = 2;",
         )
         .add_file(
-          "Child.vue",
-          r#"<template>
-  <div>Child</div>
-</template>
-<script setup>
-export const Child = {};
-export const unused = 123;
-</script>"#,
+          "index.ts",
+          "import { value } from './barrel';\nimport def from './utils';",
+        );
+
+      let (analyzer, _) = project.build();
+
+      assert_eq!(references(&analyzer, "utils.ts", "value"), vec![]);
+      assert_eq!(
+        references(&analyzer, "utils.ts", "default"),
+        vec![reference("index.ts", (2, 17), "default", "default", false)]
+      );
+    }
+
+    #[test]
+    fn glob_positions() {
+      let project = TestProject::new()
+        .add_file("pages/home.ts", "export const title = 'home';")
+        .add_file(
+          "vite.ts",
+          "// pages\nconst pages = import.meta.glob(['./pages/*.ts', '!./pages/admin.ts'], { import: 'title' });",
         )
-        .entry("index.ts");
+        .add_file(
+          "webpack.js",
+          "const pages = require.context('./pages', false, /\\.ts$/);",
+        );
 
-      let (analyzer, entries) = project.build();
+      let (analyzer, _) = project.build();
 
-      assert_reachable(&analyzer, &entries, &["index.ts", "App.vue", "Child.vue"]);
-      assert_unused(&analyzer, vec![("Child.vue", "unused")]);
+      assert_eq!(
+        references(&analyzer, "pages/home.ts", "title"),
+        vec![
+          reference("vite.ts", (2, 33), "namespace-member", "title", false),
+          reference("webpack.js", (1, 31), "namespace", "title", false),
+        ]
+      );
     }
   }
 }
@@ -0,0 +1,471 @@
+use crate::analyzer::ProjectAnalyzer;
+use anyhow::{Result, bail};
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Serialization of the module graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+  Dot,
+  Mermaid,
+  Json,
+}
+
+impl GraphFormat {
+  pub fn parse(format: &str) -> Result<Self> {
+    match format {
+      "dot" => Ok(GraphFormat::Dot),
+      "mermaid" => Ok(GraphFormat::Mermaid),
+      "json" => Ok(GraphFormat::Json),
+      _ => bail!(
+        "unknown graph format `{}`, expected dot, mermaid or json",
+        format
+      ),
+    }
+  }
+}
+
+/// Which part of the graph to export, and how coarse
+#[derive(Debug, Clone, Default)]
+pub struct GraphOptions {
+  /// Directories whose files merge into a single node named after the directory
+  pub collapse_directories: Vec<PathBuf>,
+  /// Only keep the files this one reaches, itself included
+  pub subtree: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphNode {
+  /// Source files in the node, more than one for a collapsed directory
+  pub files: usize,
+  /// Some file of the node is reachable from the entrypoints
+  pub reachable: bool,
+  pub unused_exports: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphEdge {
+  /// Kinds of every import between the two nodes, sorted
+  pub kinds: BTreeSet<&'static str>,
+  /// Every import between the two nodes is erased at runtime
+  pub type_only: bool,
+}
+
+/// The import graph as nodes and edges keyed by node id: a file path, or a collapsed
+/// directory. Sorted, so the output is stable.
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+  pub nodes: BTreeMap<String, GraphNode>,
+  pub edges: BTreeMap<(String, String), GraphEdge>,
+}
+
+impl ModuleGraph {
+  /// Build the graph from an analyzed project, with `unused_exports` counted per file
+  pub fn build(
+    analyzer: &ProjectAnalyzer,
+    reachable: &HashSet<PathBuf>,
+    unused_exports: &HashMap<PathBuf, usize>,
+    options: &GraphOptions,
+  ) -> Self {
+    let included = options
+      .subtree
+      .as_ref()
+      .map(|root| analyzer.compute_reachable(vec![root.clone()]));
+    let node_id = |file: &Path| -> String {
+      // The innermost directory wins, so nesting does not depend on the order given
+      let dir = options
+        .collapse_directories
+        .iter()
+        .filter(|dir| file.starts_with(dir))
+        .max_by_key(|dir| dir.components().count());
+      dir
+        .map_or(file, |dir| dir.as_path())
+        .to_string_lossy()
+        .to_string()
+    };
+
+    let mut graph = ModuleGraph::default();
+
+    for file in analyzer.files.keys() {
+      if included.as_ref().is_some_and(|inc| !inc.contains(file)) {
+        continue;
+      }
+      let node = graph.nodes.entry(node_id(file)).or_default();
+      node.files += 1;
+      node.reachable |= reachable.contains(file);
+      node.unused_exports += unused_exports.get(file).copied().unwrap_or(0);
+    }
+
+    for (from, targets) in &analyzer.edges {
+      for (to, imports) in targets {
+        if included
+          .as_ref()
+          .is_some_and(|inc| !inc.contains(from) || !inc.contains(to))
+        {
+          continue;
+        }
+        let (from_id, to_id) = (node_id(from), node_id(to));
+        // Imports inside a collapsed directory stay inside its node
+        if from_id == to_id && from != to {
+          continue;
+        }
+
+        let merged = graph
+          .edges
+          .entry((from_id, to_id))
+          .or_insert_with(|| GraphEdge {
+            kinds: BTreeSet::new(),
+            type_only: true,
+          });
+        for import in imports {
+          merged.kinds.insert(import.kind.as_str());
+          merged.type_only &= import.type_only;
+        }
+      }
+    }
+
+    graph
+  }
+
+  pub fn render(&self, format: GraphFormat) -> String {
+    match format {
+      GraphFormat::Dot => self.to_dot(),
+      GraphFormat::Mermaid => self.to_mermaid(),
+      GraphFormat::Json => self.to_json(),
+    }
+  }
+
+  /// Graphviz digraph: unreachable nodes dashed, type-only edges dotted
+  fn to_dot(&self) -> String {
+    let mut out = String::from("digraph modules {\n  rankdir=LR;\n  node [shape=box];\n");
+
+    for (id, node) in &self.nodes {
+      let mut attributes = vec![format!("label=\"{}\"", dot_escape(&node_label(id, node)))];
+      if !node.reachable {
+        attributes.push("style=dashed".to_string());
+        attributes.push("color=gray".to_string());
+      }
+      let _ = writeln!(out, "  \"{}\" [{}];", dot_escape(id), attributes.join(", "));
+    }
+
+    for ((from, to), edge) in &self.edges {
+      let mut attributes = vec![format!("label=\"{}\"", edge_label(edge))];
+      if edge.type_only {
+        attributes.push("style=dotted".to_string());
+      }
+      let _ = writeln!(
+        out,
+        "  \"{}\" -> \"{}\" [{}];",
+        dot_escape(from),
+        dot_escape(to),
+        attributes.join(", ")
+      );
+    }
+
+    out.push_str("}\n");
+    out
+  }
+
+  /// Mermaid flowchart: nodes get `n<index>` ids, unreachable ones the `unreachable` class,
+  /// and type-only edges are dotted
+  fn to_mermaid(&self) -> String {
+    let ids: HashMap<&String, String> = self
+      .nodes
+      .keys()
+      .enumerate()
+      .map(|(i, id)| (id, format!("n{}", i)))
+      .collect();
+    let mut out = String::from("flowchart LR\n");
+
+    for (id, node) in &self.nodes {
+      let _ = writeln!(
+        out,
+        "  {}[\"{}\"]",
+        ids[id],
+        mermaid_escape(&node_label(id, node))
+      );
+    }
+
+    for ((from, to), edge) in &self.edges {
+      let arrow = if edge.type_only { "-.->" } else { "-->" };
+      let _ = writeln!(
+        out,
+        "  {} {}|{}| {}",
+        ids[from],
+        arrow,
+        edge_label(edge),
+        ids[to]
+      );
+    }
+
+    let unreachable: Vec<&str> = self
+      .nodes
+      .iter()
+      .filter(|(_, node)| !node.reachable)
+      .map(|(id, _)| ids[id].as_str())
+      .collect();
+    if !unreachable.is_empty() {
+      out.push_str("  classDef unreachable stroke-dasharray: 5 5,color:#888\n");
+      let _ = writeln!(out, "  class {} unreachable", unreachable.join(","));
+    }
+
+    out
+  }
+
+  /// Adjacency lists keyed by node id, each node with its stats and outgoing imports
+  fn to_json(&self) -> String {
+    let mut nodes = Map::new();
+
+    for (id, node) in &self.nodes {
+      let imports: Vec<Value> = self
+        .edges
+        .range((id.clone(), String::new())..)
+        .take_while(|((from, _), _)| from == id)
+        .map(|((_, to), edge)| {
+          json!({
+            "to": to,
+            "kinds": edge.kinds,
+            "typeOnly": edge.type_only,
+          })
+        })
+        .collect();
+
+      nodes.insert(
+        id.clone(),
+        json!({
+          "files": node.files,
+          "reachable": node.reachable,
+          "unusedExports": node.unused_exports,
+          "imports": imports,
+        }),
+      );
+    }
+
+    serde_json::to_string_pretty(&json!({ "nodes": nodes })).unwrap_or_default()
+  }
+}
+
+fn node_label(id: &str, node: &GraphNode) -> String {
+  let mut details = Vec::new();
+  if node.files > 1 {
+    details.push(format!("{} files", node.files));
+  }
+  match node.unused_exports {
+    0 => {}
+    1 => details.push("1 unused export".to_string()),
+    n => details.push(format!("{} unused exports", n)),
+  }
+
+  if details.is_empty() {
+    id.to_string()
+  } else {
+    format!("{} ({})", id, details.join(", "))
+  }
+}
+
+fn edge_label(edge: &GraphEdge) -> String {
+  edge.kinds.iter().copied().collect::<Vec<_>>().join(", ")
+}
+
+fn dot_escape(s: &str) -> String {
+  s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(s: &str) -> String {
+  s.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn project() -> ProjectAnalyzer {
+    let sources: HashMap<PathBuf, &str> = [
+      (
+        "index.ts",
+        "import { Button } from './components/Button';\nimport type { User } from './types';",
+      ),
+      (
+        "components/Button.ts",
+        "import { Icon } from './Icon';\nexport const Button = Icon;",
+      ),
+      (
+        "components/Icon.ts",
+        "export const Icon = 1;\nexport const unused = 2;",
+      ),
+      ("types.ts", "export interface User {}"),
+      ("dead.ts", "export const dead = () => import('./types');"),
+    ]
+    .into_iter()
+    .map(|(path, source)| (PathBuf::from(path), source))
+    .collect();
+
+    ProjectAnalyzer::from_sources(&sources, None).unwrap()
+  }
+
+  fn build(options: &GraphOptions) -> ModuleGraph {
+    let analyzer = project();
+    let reachable = analyzer.compute_reachable(vec![PathBuf::from("index.ts")]);
+    let mut unused_exports = HashMap::new();
+    for (path, _) in analyzer.find_unused_exports_with(Some(&reachable)) {
+      *unused_exports.entry(path).or_default() += 1;
+    }
+    ModuleGraph::build(&analyzer, &reachable, &unused_exports, options)
+  }
+
+  #[test]
+  fn dot_output() {
+    let graph = build(&GraphOptions::default());
+
+    assert_eq!(
+      graph.render(GraphFormat::Dot),
+      r#"digraph modules {
+  rankdir=LR;
+  node [shape=box];
+  "components/Button.ts" [label="components/Button.ts"];
+  "components/Icon.ts" [label="components/Icon.ts (1 unused export)"];
+  "dead.ts" [label="dead.ts (1 unused export)", style=dashed, color=gray];
+  "index.ts" [label="index.ts"];
+  "types.ts" [label="types.ts"];
+  "components/Button.ts" -> "components/Icon.ts" [label="import"];
+  "dead.ts" -> "types.ts" [label="dynamic-import"];
+  "index.ts" -> "components/Button.ts" [label="import"];
+  "index.ts" -> "types.ts" [label="import", style=dotted];
+}
+"#
+    );
+  }
+
+  #[test]
+  fn mermaid_output_with_collapsed_directory() {
+    let graph = build(&GraphOptions {
+      collapse_directories: vec![PathBuf::from("components")],
+      ..Default::default()
+    });
+
+    assert_eq!(
+      graph.render(GraphFormat::Mermaid),
+      r#"flowchart LR
+  n0["components (2 files, 1 unused export)"]
+  n1["dead.ts (1 unused export)"]
+  n2["index.ts"]
+  n3["types.ts"]
+  n1 -->|dynamic-import| n3
+  n2 -->|import| n0
+  n2 -.->|import| n3
+  classDef unreachable stroke-dasharray: 5 5,color:#888
+  class n1 unreachable
+"#
+    );
+  }
+
+  #[test]
+  fn nested_collapsed_directories() {
+    let sources: HashMap<PathBuf, &str> = [
+      ("src/index.ts", "import './ui/Button';"),
+      ("src/ui/Button.ts", "import './icons/Icon';"),
+      ("src/ui/icons/Icon.ts", "export {};"),
+      ("src/ui/icons/Arrow.ts", "export {};"),
+    ]
+    .into_iter()
+    .map(|(path, source)| (PathBuf::from(path), source))
+    .collect();
+    let analyzer = ProjectAnalyzer::from_sources(&sources, None).unwrap();
+    let reachable = analyzer.compute_reachable(vec![PathBuf::from("src/index.ts")]);
+
+    for collapse in [["src/ui", "src/ui/icons"], ["src/ui/icons", "src/ui"]] {
+      let options = GraphOptions {
+        collapse_directories: collapse.iter().map(PathBuf::from).collect(),
+        ..Default::default()
+      };
+      let graph = ModuleGraph::build(&analyzer, &reachable, &HashMap::new(), &options);
+
+      assert_eq!(
+        graph.render(GraphFormat::Mermaid),
+        r#"flowchart LR
+  n0["src/index.ts"]
+  n1["src/ui"]
+  n2["src/ui/icons (2 files)"]
+  n0 -->|import| n1
+  n1 -->|import| n2
+"#
+      );
+    }
+  }
+
+  #[test]
+  fn every_import_kind_between_two_files() {
+    let sources: HashMap<PathBuf, &str> = [
+      (
+        "a.ts",
+        "import type { B } from './b';\nconst lazy = () => import('./b');\nrequire('./b');",
+      ),
+      ("b.ts", "export type B = 1;"),
+      (
+        "c.ts",
+        "import type { B } from './b';\nexport type { B } from './b';",
+      ),
+    ]
+    .into_iter()
+    .map(|(path, source)| (PathBuf::from(path), source))
+    .collect();
+    let analyzer = ProjectAnalyzer::from_sources(&sources, None).unwrap();
+    let reachable = analyzer.compute_reachable(vec![PathBuf::from("a.ts")]);
+    let graph = ModuleGraph::build(
+      &analyzer,
+      &reachable,
+      &HashMap::new(),
+      &GraphOptions::default(),
+    );
+
+    assert_eq!(
+      graph.render(GraphFormat::Dot),
+      r#"digraph modules {
+  rankdir=LR;
+  node [shape=box];
+  "a.ts" [label="a.ts"];
+  "b.ts" [label="b.ts"];
+  "c.ts" [label="c.ts", style=dashed, color=gray];
+  "a.ts" -> "b.ts" [label="dynamic-import, import, require"];
+  "c.ts" -> "b.ts" [label="import, re-export", style=dotted];
+}
+"#
+    );
+  }
+
+  #[test]
+  fn json_output_of_a_subtree() {
+    let graph = build(&GraphOptions {
+      subtree: Some(PathBuf::from("components/Button.ts")),
+      ..Default::default()
+    });
+
+    let json: Value = serde_json::from_str(&graph.render(GraphFormat::Json)).unwrap();
+    assert_eq!(
+      json,
+      json!({
+        "nodes": {
+          "components/Button.ts": {
+            "files": 1,
+            "reachable": true,
+            "unusedExports": 0,
+            "imports": [{ "to": "components/Icon.ts", "kinds": ["import"], "typeOnly": false }],
+          },
+          "components/Icon.ts": {
+            "files": 1,
+            "reachable": true,
+            "unusedExports": 1,
+            "imports": [],
+          },
+        }
+      })
+    );
+  }
+
+  #[test]
+  fn unknown_format() {
+    assert!(GraphFormat::parse("svg").is_err());
+  }
+}
@@ -1,28 +1,242 @@
 use napi_derive::napi;
 mod analyzer;
+mod graph;
+mod package_json;
+mod tsconfig;
 
-use analyzer::{ProjectAnalyzer, SUPPORTED_EXTENSIONS, SweepyConfig as AnalyzerConfig};
+use analyzer::{
+  ExportInfo, ProjectAnalyzer, SUPPORTED_EXTENSIONS, SweepyConfig as AnalyzerConfig,
+  UnusedFilesOptions,
+};
 use anyhow::Result;
-use std::collections::HashMap;
+use graph::{GraphFormat, GraphOptions, ModuleGraph};
+use package_json::PackageJson;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use tsconfig::{TsconfigPaths, load_tsconfig};
 use walkdir::WalkDir;
 
 #[napi(object)]
 pub struct UnusedExport {
   pub file: String,
   pub name: String,
+  /// function, class, variable, type, interface, enum, namespace, default or re-export
+  pub kind: String,
+  /// 1-based line of the exported name
+  pub line: u32,
+  /// 1-based column of the exported name, in UTF-16 code units
+  pub column: u32,
+  /// Byte offsets of the exported name in the file
+  pub start: u32,
+  pub end: u32,
+}
+
+/// A value export that is only ever used as a type, and could be `export type`
+#[napi(object)]
+pub struct TypeOnlyExport {
+  pub file: String,
+  pub name: String,
+  pub kind: String,
+  pub line: u32,
+  pub column: u32,
+  /// Files importing it as a value while only using it in type positions, which could
+  /// switch to `import type`
+  pub importers: Vec<String>,
+}
+
+/// A relative or aliased import that matches no project file
+#[napi(object)]
+pub struct UnresolvedImport {
+  pub file: String,
+  pub specifier: String,
+  /// no-matching-file, alias-target-missing, extension-mismatch or case-mismatch
+  pub reason: String,
+  /// 1-based position of the specifier
+  pub line: u32,
+  pub column: u32,
+}
+
+/// A package.json dependency no reachable file imports
+#[napi(object)]
+pub struct UnusedDependency {
+  pub name: String,
+  /// dependencies, devDependencies or peerDependencies
+  pub kind: String,
+}
+
+/// Files importing each other in a loop
+#[napi(object)]
+pub struct CircularDependency {
+  /// runtime, or type when the loop only closes through type-only imports
+  pub kind: String,
+  /// Every file of the strongly connected component
+  pub files: Vec<String>,
+  /// A shortest loop through the files, the last one importing the first
+  pub path: Vec<CycleEdge>,
+}
+
+/// A file in a cycle and how it imports the next one
+#[napi(object)]
+pub struct CycleEdge {
+  pub file: String,
+  pub specifier: String,
+  pub type_only: bool,
+}
+
+/// A package imported by source files, flagged against their nearest package.json
+#[napi(object)]
+pub struct DependencyImport {
+  pub name: String,
+  /// The nearest package.json of the importing files
+  pub package_json: String,
+  pub importers: Vec<String>,
+}
+
+/// A module consuming an export, directly or through re-exports
+#[napi(object)]
+pub struct ExportReference {
+  pub file: String,
+  /// 1-based position of the import specifier, or of the re-exported name
+  pub line: u32,
+  pub column: u32,
+  /// named, default, namespace, namespace-member or re-export
+  pub kind: String,
+  /// Specifier as written in `file`
+  pub specifier: String,
+  /// Name the export goes by in the module `specifier` points to
+  pub name: String,
+  pub type_only: bool,
+}
+
+/// What a change to a set of files affects
+#[napi(object)]
+pub struct AffectedFiles {
+  /// The changed files and every file importing them, directly or not, sorted
+  pub files: Vec<String>,
+  /// Entrypoints among `files`, sorted
+  pub entries: Vec<String>,
+}
+
+/// A chain of imports from an entrypoint to a file
+#[napi(object)]
+pub struct ImportPath {
+  pub entry: String,
+  /// Each import along the way, in order; empty when the file is the entrypoint
+  pub hops: Vec<ImportHop>,
+}
+
+/// One import along an import path, the runtime one when the files share several
+#[napi(object)]
+pub struct ImportHop {
+  pub from: String,
+  pub to: String,
+  /// Specifier `from` imports `to` with
+  pub specifier: String,
+  /// import, dynamic-import, require, import-equals, glob or re-export
+  pub kind: String,
+  pub type_only: bool,
+}
+
+/// A name exported by an entrypoint treated as public API
+#[napi(object)]
+pub struct PublicExport {
+  pub entry: String,
+  pub name: String,
+  /// File declaring it, once re-exports are followed
+  pub file: String,
+  /// Name and position of the declaration in `file`
+  pub local: String,
+  pub kind: String,
+  pub line: u32,
+  pub column: u32,
 }
 
 #[napi(object)]
 pub struct SweepyResult {
   pub reachable_files: Vec<String>,
+  pub unused_files: Vec<String>,
+  /// Unused value exports: functions, classes, variables, enums, namespaces and defaults
   pub unused_exports: Vec<UnusedExport>,
+  /// Unused type aliases and interfaces, and type-only exports
+  pub unused_types: Vec<UnusedExport>,
+  /// Value exports only used as types
+  pub type_only_exports: Vec<TypeOnlyExport>,
+  /// Exports not imported anywhere but used inside their own file, which could be un-exported.
+  /// They are left out of `unusedExports` and `unusedTypes`.
+  pub used_in_file_exports: Vec<UnusedExport>,
+  /// Exports of the entrypoints when `publicEntryExports` is set, sorted by entry and name
+  pub public_api: Vec<PublicExport>,
+  /// Dependencies of the root package.json never imported by a reachable file
+  pub unused_dependencies: Vec<UnusedDependency>,
+  /// Packages reachable files import that their nearest package.json does not declare
+  pub unlisted_dependencies: Vec<DependencyImport>,
+  /// Packages only in `devDependencies` but imported at runtime by reachable files that are
+  /// not tests, config or declaration files
+  pub misplaced_dependencies: Vec<DependencyImport>,
+  /// Relative and aliased imports of every file that match no project file
+  pub unresolved_imports: Vec<UnresolvedImport>,
+  /// Import cycles, runtime ones first
+  pub cycles: Vec<CircularDependency>,
 }
 
 #[napi(object)]
+#[derive(Default)]
 pub struct SweepyConfig {
   pub alias: Option<HashMap<String, String>>,
+  /// Path to the tsconfig.json providing `baseUrl` and `paths`, defaults to `<root>/tsconfig.json`
+  pub tsconfig: Option<String>,
+  pub unused_files: Option<UnusedFilesConfig>,
+  /// Only count imports made by files reachable from the entries when deciding export usage
+  pub reachable_imports_only: Option<bool>,
+  /// Leave exports used inside their own file out of the results entirely
+  pub ignore_exports_used_in_file: Option<bool>,
+  /// Treat exports of the entrypoints, and everything they re-export, as used public API
+  pub public_entry_exports: Option<bool>,
+}
+
+#[napi(object)]
+#[derive(Default)]
+pub struct ExportGraphOptions {
+  /// dot, mermaid or json (the default)
+  pub format: Option<String>,
+  /// Directories whose files merge into a single node
+  pub collapse_directories: Option<Vec<String>>,
+  /// Only export the files this one reaches, itself included
+  pub subtree: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Default)]
+pub struct UnusedFilesConfig {
+  /// Skip tool configuration files such as `vite.config.ts` or `.eslintrc.js`
+  pub ignore_config_files: Option<bool>,
+  /// Skip `.d.ts` declaration files
+  pub ignore_declaration_files: Option<bool>,
+  /// Skip `*.test.*`/`*.spec.*` files and files under `__tests__` or `__mocks__`
+  pub ignore_test_files: Option<bool>,
+}
+
+/// Load `baseUrl`/`paths` from the configured tsconfig, or `<root>/tsconfig.json` when present
+fn load_tsconfig_paths(root: &Path, tsconfig: Option<&str>) -> Option<TsconfigPaths> {
+  let tsconfig_path = match tsconfig {
+    Some(p) => root.join(p),
+    None => {
+      let default = root.join("tsconfig.json");
+      if !default.is_file() {
+        return None;
+      }
+      default
+    }
+  };
+
+  match load_tsconfig(root, &tsconfig_path) {
+    Ok(paths) => Some(paths),
+    Err(err) => {
+      eprintln!("Ignoring tsconfig paths: {:#}", err);
+      None
+    }
+  }
 }
 
 /// Collect all ts/tsx/js/jsx files under root
@@ -40,56 +254,428 @@ fn collect_source_files(root: &Path) -> Result<HashMap<PathBuf, String>> {
   Ok(files)
 }
 
-#[napi]
-pub fn sweepy(_root: String, entries: Vec<String>, config: Option<SweepyConfig>) -> SweepyResult {
-  let root = PathBuf::from(_root);
+/// Collect package.json files under root (outside node_modules), keyed by their directory
+/// relative to root
+fn collect_package_jsons(root: &Path) -> HashMap<PathBuf, PackageJson> {
+  let mut packages = HashMap::new();
+  let walker = WalkDir::new(root)
+    .into_iter()
+    .filter_entry(|e| e.file_name() != "node_modules");
+
+  for entry in walker.filter_map(|e| e.ok()) {
+    let p = entry.path();
+    if p.file_name().and_then(|s| s.to_str()) != Some("package.json") {
+      continue;
+    }
+
+    let parsed = fs::read_to_string(p)
+      .map_err(anyhow::Error::from)
+      .and_then(|content| PackageJson::parse(&content));
+
+    match parsed {
+      Ok(pkg) => {
+        let dir = p.parent().unwrap_or(root);
+        let relative_dir = dir.strip_prefix(root).unwrap_or(dir).to_path_buf();
+        packages.insert(relative_dir, pkg);
+      }
+      Err(err) => eprintln!("Ignoring {}: {:#}", p.display(), err),
+    }
+  }
+
+  packages
+}
+
+/// Paths made relative to root, the way the analyzer keys files
+fn relative_paths(root: &Path, paths: &[String]) -> Vec<PathBuf> {
+  paths
+    .iter()
+    .map(|e| {
+      let p = PathBuf::from(e);
+      p.strip_prefix(root).unwrap_or(&p).to_path_buf()
+    })
+    .collect()
+}
 
-  let sources = collect_source_files(&root).expect("Failed to collect source files");
+/// Parse every source file under root and build the import graph
+fn analyze_project(
+  root: &Path,
+  config: &SweepyConfig,
+  public_entries: Vec<PathBuf>,
+) -> ProjectAnalyzer {
+  let sources = collect_source_files(root).expect("Failed to collect source files");
   let sources_ref: HashMap<PathBuf, &str> = sources
     .iter()
     .map(|(p, c)| {
-      let relative_path = p.strip_prefix(&root).unwrap_or(p).to_path_buf();
+      let relative_path = p.strip_prefix(root).unwrap_or(p).to_path_buf();
       (relative_path, c.as_str())
     })
     .collect();
 
-  let analyzer_config = config.map(|c| AnalyzerConfig {
-    alias: c.alias.unwrap_or_default(),
+  let analyzer_config = Some(AnalyzerConfig {
+    tsconfig: load_tsconfig_paths(root, config.tsconfig.as_deref()),
+    alias: config.alias.clone().unwrap_or_default(),
+    packages: collect_package_jsons(root),
+    unused_files: config
+      .unused_files
+      .as_ref()
+      .map(|u| UnusedFilesOptions {
+        ignore_config_files: u.ignore_config_files.unwrap_or(false),
+        ignore_declaration_files: u.ignore_declaration_files.unwrap_or(false),
+        ignore_test_files: u.ignore_test_files.unwrap_or(false),
+      })
+      .unwrap_or_default(),
+    public_entries,
   });
 
-  let analyzer = ProjectAnalyzer::from_sources(&sources_ref, analyzer_config)
-    .expect("Failed to analyze project");
-  let entrypoints: Vec<PathBuf> = entries
-    .iter()
-    .map(|e| {
-      let p = PathBuf::from(e);
-      p.strip_prefix(&root).unwrap_or(&p).to_path_buf()
+  ProjectAnalyzer::from_sources(&sources_ref, analyzer_config).expect("Failed to analyze project")
+}
+
+/// Entrypoints whose exports count as used, with `publicEntryExports`
+fn public_entries(config: &SweepyConfig, entrypoints: &[PathBuf]) -> Vec<PathBuf> {
+  if config.public_entry_exports.unwrap_or(false) {
+    entrypoints.to_vec()
+  } else {
+    Vec::new()
+  }
+}
+
+/// The result list an unused export is reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnusedCategory {
+  Export,
+  Type,
+  UsedInFile,
+}
+
+/// Unused exports with the list each goes in, honoring `reachableImportsOnly` and
+/// dropping exports used in their own file with `ignoreExportsUsedInFile`
+fn classify_unused_exports(
+  analyzer: &ProjectAnalyzer,
+  reachable: &HashSet<PathBuf>,
+  config: &SweepyConfig,
+) -> Vec<(PathBuf, ExportInfo, UnusedCategory)> {
+  let ignore_exports_used_in_file = config.ignore_exports_used_in_file.unwrap_or(false);
+  let unused = if config.reachable_imports_only.unwrap_or(false) {
+    analyzer.find_unused_exports_with(Some(reachable))
+  } else {
+    analyzer.find_unused_exports()
+  };
+
+  unused
+    .into_iter()
+    .filter(|(_, export)| !(export.used_in_file && ignore_exports_used_in_file))
+    .map(|(path, export)| {
+      let category = if export.used_in_file {
+        UnusedCategory::UsedInFile
+      } else if analyzer.is_type_export(&path, &export) {
+        UnusedCategory::Type
+      } else {
+        UnusedCategory::Export
+      };
+      (path, export, category)
     })
-    .collect();
+    .collect()
+}
+
+#[napi]
+pub fn sweepy(_root: String, entries: Vec<String>, config: Option<SweepyConfig>) -> SweepyResult {
+  let root = PathBuf::from(_root);
+
+  let config = config.unwrap_or_default();
+  let entrypoints = relative_paths(&root, &entries);
+  let analyzer = analyze_project(&root, &config, public_entries(&config, &entrypoints));
 
   let reachable = analyzer.compute_reachable(entrypoints);
-  let unused_exports_raw = analyzer.find_unused_exports();
+  let live = config
+    .reachable_imports_only
+    .unwrap_or(false)
+    .then_some(&reachable);
+  let unused_exports_raw = classify_unused_exports(&analyzer, &reachable, &config);
 
-  let mut reachable_files: Vec<String> = reachable
+  let type_only_exports: Vec<TypeOnlyExport> = analyzer
+    .find_type_only_used_exports(live)
     .into_iter()
-    .map(|p| p.to_string_lossy().to_string())
+    .map(|(path, export, importers)| TypeOnlyExport {
+      file: path.to_string_lossy().to_string(),
+      name: export.name,
+      kind: export.kind.as_str().to_string(),
+      line: export.line,
+      column: export.column,
+      importers: importers
+        .into_iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect(),
+    })
     .collect();
 
-  reachable_files.sort();
-
-  let mut unused_exports: Vec<UnusedExport> = unused_exports_raw
+  let public_api: Vec<PublicExport> = analyzer
+    .find_public_api()
     .into_iter()
-    .map(|(path, name)| UnusedExport {
+    .map(|(entry, name, path, export)| PublicExport {
+      entry: entry.to_string_lossy().to_string(),
+      name,
       file: path.to_string_lossy().to_string(),
+      local: export.name,
+      kind: export.kind.as_str().to_string(),
+      line: export.line,
+      column: export.column,
+    })
+    .collect();
+
+  let unused_dependencies: Vec<UnusedDependency> = analyzer
+    .find_unused_dependencies(&reachable)
+    .into_iter()
+    .map(|(name, kind)| UnusedDependency {
+      name,
+      kind: kind.as_str().to_string(),
+    })
+    .collect();
+
+  let to_dependency_import =
+    |(name, dir, importers): (String, PathBuf, Vec<PathBuf>)| DependencyImport {
       name,
+      package_json: dir.join("package.json").to_string_lossy().to_string(),
+      importers: importers
+        .into_iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect(),
+    };
+  let unlisted_dependencies: Vec<DependencyImport> = analyzer
+    .find_unlisted_dependencies(&reachable)
+    .into_iter()
+    .map(to_dependency_import)
+    .collect();
+  let misplaced_dependencies: Vec<DependencyImport> = analyzer
+    .find_misplaced_dependencies(&reachable)
+    .into_iter()
+    .map(to_dependency_import)
+    .collect();
+
+  let unresolved_imports: Vec<UnresolvedImport> = analyzer
+    .find_unresolved_imports()
+    .into_iter()
+    .map(
+      |(path, specifier, reason, (line, column))| UnresolvedImport {
+        file: path.to_string_lossy().to_string(),
+        specifier,
+        reason: reason.as_str().to_string(),
+        line,
+        column,
+      },
+    )
+    .collect();
+
+  let cycles: Vec<CircularDependency> = analyzer
+    .find_cycles()
+    .into_iter()
+    .map(|cycle| CircularDependency {
+      kind: if cycle.type_only { "type" } else { "runtime" }.to_string(),
+      files: cycle
+        .files
+        .into_iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect(),
+      path: cycle
+        .path
+        .into_iter()
+        .map(|(file, edge)| CycleEdge {
+          file: file.to_string_lossy().to_string(),
+          specifier: edge.specifier,
+          type_only: edge.type_only,
+        })
+        .collect(),
     })
     .collect();
 
+  let unused_files: Vec<String> = analyzer
+    .find_unused_files(&reachable)
+    .into_iter()
+    .map(|p| p.to_string_lossy().to_string())
+    .collect();
+
+  let mut reachable_files: Vec<String> = reachable
+    .into_iter()
+    .map(|p| p.to_string_lossy().to_string())
+    .collect();
+
+  reachable_files.sort();
+
+  let mut unused_exports: Vec<UnusedExport> = Vec::new();
+  let mut unused_types: Vec<UnusedExport> = Vec::new();
+  let mut used_in_file_exports: Vec<UnusedExport> = Vec::new();
+
+  for (path, export, category) in unused_exports_raw {
+    let unused = UnusedExport {
+      file: path.to_string_lossy().to_string(),
+      kind: export.kind.as_str().to_string(),
+      line: export.line,
+      column: export.column,
+      start: export.span.start,
+      end: export.span.end,
+      name: export.name,
+    };
+
+    match category {
+      UnusedCategory::Export => unused_exports.push(unused),
+      UnusedCategory::Type => unused_types.push(unused),
+      UnusedCategory::UsedInFile => used_in_file_exports.push(unused),
+    }
+  }
+
   // sort unused exports by file and then by name
   unused_exports.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.name.cmp(&b.name)));
+  unused_types.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.name.cmp(&b.name)));
+  used_in_file_exports.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.name.cmp(&b.name)));
 
   SweepyResult {
     reachable_files,
+    unused_files,
     unused_exports,
+    unused_types,
+    type_only_exports,
+    used_in_file_exports,
+    public_api,
+    unused_dependencies,
+    unlisted_dependencies,
+    misplaced_dependencies,
+    unresolved_imports,
+    cycles,
+  }
+}
+
+/// Explain why `file` is reachable: the shortest import path from an entrypoint to it, or
+/// up to `max_paths` of them, shortest first. Empty when the file is not reachable.
+#[napi]
+pub fn why_reachable(
+  root: String,
+  entries: Vec<String>,
+  file: String,
+  config: Option<SweepyConfig>,
+  max_paths: Option<u32>,
+) -> Vec<ImportPath> {
+  let root = PathBuf::from(root);
+  let config = config.unwrap_or_default();
+  let analyzer = analyze_project(&root, &config, Vec::new());
+  let target = relative_paths(&root, &[file]).remove(0);
+
+  analyzer
+    .find_import_paths(
+      relative_paths(&root, &entries),
+      &target,
+      max_paths.unwrap_or(1) as usize,
+    )
+    .into_iter()
+    .map(|files| ImportPath {
+      entry: files[0].to_string_lossy().to_string(),
+      hops: files
+        .windows(2)
+        .map(|pair| {
+          let edge = analyzer.edge(&pair[0], &pair[1]).unwrap();
+          ImportHop {
+            from: pair[0].to_string_lossy().to_string(),
+            to: pair[1].to_string_lossy().to_string(),
+            specifier: edge.specifier.clone(),
+            kind: edge.kind.as_str().to_string(),
+            type_only: edge.type_only,
+          }
+        })
+        .collect(),
+    })
+    .collect()
+}
+
+/// The files and entrypoints affected by a change to `changed_files`, following imports in
+/// reverse. Deleted files and assets affect the files whose imports would match them.
+#[napi]
+pub fn affected_entries(
+  root: String,
+  entries: Vec<String>,
+  changed_files: Vec<String>,
+  config: Option<SweepyConfig>,
+) -> AffectedFiles {
+  let root = PathBuf::from(root);
+  let config = config.unwrap_or_default();
+  let analyzer = analyze_project(&root, &config, Vec::new());
+
+  let (files, entries) = analyzer.find_affected(
+    &relative_paths(&root, &entries),
+    &relative_paths(&root, &changed_files),
+  );
+  let to_strings = |paths: Vec<PathBuf>| -> Vec<String> {
+    paths
+      .iter()
+      .map(|p| p.to_string_lossy().to_string())
+      .collect()
+  };
+
+  AffectedFiles {
+    files: to_strings(files),
+    entries: to_strings(entries),
+  }
+}
+
+/// Every module consuming export `export_name` of `file`, following re-exports through
+/// barrels and namespace imports, sorted by file and position
+#[napi]
+pub fn find_references(
+  root: String,
+  file: String,
+  export_name: String,
+  config: Option<SweepyConfig>,
+) -> Vec<ExportReference> {
+  let root = PathBuf::from(root);
+  let config = config.unwrap_or_default();
+  let analyzer = analyze_project(&root, &config, Vec::new());
+  let module = relative_paths(&root, &[file]).remove(0);
+
+  analyzer
+    .find_references(&module, &export_name)
+    .into_iter()
+    .map(|reference| ExportReference {
+      file: reference.file.to_string_lossy().to_string(),
+      line: reference.line,
+      column: reference.column,
+      kind: reference.kind.as_str().to_string(),
+      specifier: reference.specifier,
+      name: reference.name,
+      type_only: reference.type_only,
+    })
+    .collect()
+}
+
+/// Serialize the module graph as Graphviz DOT, a Mermaid flowchart or JSON adjacency lists.
+/// Nodes carry reachability from the entries and unused export counts, edges the import kinds.
+#[napi]
+pub fn export_graph(
+  root: String,
+  entries: Vec<String>,
+  options: Option<ExportGraphOptions>,
+  config: Option<SweepyConfig>,
+) -> napi::Result<String> {
+  let root = PathBuf::from(root);
+  let options = options.unwrap_or_default();
+  let config = config.unwrap_or_default();
+  let format = GraphFormat::parse(options.format.as_deref().unwrap_or("json"))
+    .map_err(|err| napi::Error::from_reason(err.to_string()))?;
+
+  let entrypoints = relative_paths(&root, &entries);
+  let analyzer = analyze_project(&root, &config, public_entries(&config, &entrypoints));
+  let reachable = analyzer.compute_reachable(entrypoints);
+
+  // Counted like `sweepy` lists them in `unusedExports`
+  let mut unused_exports: HashMap<PathBuf, usize> = HashMap::new();
+  for (path, _, category) in classify_unused_exports(&analyzer, &reachable, &config) {
+    if category == UnusedCategory::Export {
+      *unused_exports.entry(path).or_default() += 1;
+    }
   }
+
+  let graph_options = GraphOptions {
+    collapse_directories: relative_paths(&root, &options.collapse_directories.unwrap_or_default()),
+    subtree: options
+      .subtree
+      .map(|subtree| relative_paths(&root, &[subtree]).remove(0)),
+  };
+
+  Ok(ModuleGraph::build(&analyzer, &reachable, &unused_exports, &graph_options).render(format))
 }
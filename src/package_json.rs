@@ -0,0 +1,75 @@
+use anyhow::Result;
+use serde_json::Value;
+
+/// The package.json fields used during analysis
+#[derive(Debug, Clone, Default)]
+pub struct PackageJson {
+  pub name: Option<String>,
+  pub main: Option<String>,
+  /// `types`, or the older `typings` alias
+  pub types: Option<String>,
+  pub dependencies: Vec<String>,
+  pub dev_dependencies: Vec<String>,
+  pub peer_dependencies: Vec<String>,
+}
+
+/// The package.json field a dependency is declared in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyKind {
+  Dependencies,
+  DevDependencies,
+  PeerDependencies,
+}
+
+impl DependencyKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      DependencyKind::Dependencies => "dependencies",
+      DependencyKind::DevDependencies => "devDependencies",
+      DependencyKind::PeerDependencies => "peerDependencies",
+    }
+  }
+}
+
+impl PackageJson {
+  pub fn parse(content: &str) -> Result<Self> {
+    let json: Value = serde_json::from_str(content)?;
+    let field = |key: &str| json.get(key).and_then(|v| v.as_str()).map(String::from);
+    let names = |key: &str| -> Vec<String> {
+      json
+        .get(key)
+        .and_then(|v| v.as_object())
+        .map(|deps| deps.keys().cloned().collect())
+        .unwrap_or_default()
+    };
+
+    Ok(Self {
+      name: field("name"),
+      main: field("main"),
+      types: field("types").or_else(|| field("typings")),
+      dependencies: names("dependencies"),
+      dev_dependencies: names("devDependencies"),
+      peer_dependencies: names("peerDependencies"),
+    })
+  }
+
+  /// Entry files to try when a directory containing this package.json is imported
+  pub fn entry_fields(&self) -> impl Iterator<Item = &str> {
+    self
+      .types
+      .iter()
+      .chain(self.main.iter())
+      .map(|s| s.as_str())
+  }
+
+  /// Every declared dependency with the field declaring it
+  pub fn all_dependencies(&self) -> impl Iterator<Item = (&str, DependencyKind)> {
+    [
+      (&self.dependencies, DependencyKind::Dependencies),
+      (&self.dev_dependencies, DependencyKind::DevDependencies),
+      (&self.peer_dependencies, DependencyKind::PeerDependencies),
+    ]
+    .into_iter()
+    .flat_map(|(deps, kind)| deps.iter().map(move |d| (d.as_str(), kind)))
+  }
+}
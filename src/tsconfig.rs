@@ -0,0 +1,437 @@
+use anyhow::{Context, Result, bail};
+use path_clean::PathClean;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A single `compilerOptions.paths` entry: `"@/*": ["src/*", "generated/*"]`
+#[derive(Debug, Clone)]
+pub struct PathMapping {
+  pub pattern: String,
+  pub targets: Vec<String>,
+}
+
+/// Module resolution settings taken from a tsconfig.json and its `extends` chain.
+/// All paths are relative to the project root.
+#[derive(Debug, Clone, Default)]
+pub struct TsconfigPaths {
+  pub base_url: Option<PathBuf>,
+  pub paths: Vec<PathMapping>,
+  /// Directory `paths` targets are relative to: `baseUrl` when set, otherwise the
+  /// directory of the tsconfig declaring `paths`
+  pub paths_base: PathBuf,
+}
+
+impl TsconfigPaths {
+  /// Candidate module paths for a non-relative specifier, in the order tsc tries them:
+  /// every target of the best matching `paths` pattern, then `baseUrl`
+  pub fn candidates(&self, spec: &str) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    if let Some((mapping, captured)) = self.match_pattern(spec) {
+      for target in &mapping.targets {
+        let substituted = target.replacen('*', captured, 1);
+        candidates.push(self.paths_base.join(substituted).clean());
+      }
+    }
+
+    if let Some(base_url) = &self.base_url {
+      candidates.push(base_url.join(spec).clean());
+    }
+
+    candidates
+  }
+
+  /// Whether a `paths` pattern maps `spec` into the project. A catch-all `*` pattern does
+  /// not count, since tsc falls back to packages when it matches nothing.
+  pub fn maps(&self, spec: &str) -> bool {
+    self
+      .match_pattern(spec)
+      .is_some_and(|(mapping, _)| mapping.pattern != "*")
+  }
+
+  /// Pick the `paths` pattern tsc would use: an exact match first, otherwise the
+  /// wildcard pattern with the longest prefix. Returns the text captured by `*`.
+  fn match_pattern<'a>(&'a self, spec: &'a str) -> Option<(&'a PathMapping, &'a str)> {
+    if let Some(exact) = self
+      .paths
+      .iter()
+      .find(|m| !m.pattern.contains('*') && m.pattern == spec)
+    {
+      return Some((exact, ""));
+    }
+
+    let mut best: Option<(&PathMapping, &str)> = None;
+    let mut best_prefix_len = 0;
+
+    for mapping in &self.paths {
+      if let Some((prefix, suffix)) = mapping.pattern.split_once('*')
+        && spec.len() >= prefix.len() + suffix.len()
+        && spec.starts_with(prefix)
+        && spec.ends_with(suffix)
+        && (best.is_none() || prefix.len() > best_prefix_len)
+      {
+        best_prefix_len = prefix.len();
+        best = Some((mapping, &spec[prefix.len()..spec.len() - suffix.len()]));
+      }
+    }
+
+    best
+  }
+}
+
+/// `compilerOptions` we care about, with paths already resolved against the
+/// tsconfig that declared them
+#[derive(Debug, Default)]
+struct RawCompilerOptions {
+  base_url: Option<PathBuf>,
+  paths: Option<(Vec<PathMapping>, PathBuf)>,
+}
+
+impl RawCompilerOptions {
+  /// Apply `other` on top of `self`, the way a child config overrides its base
+  fn merge(&mut self, other: RawCompilerOptions) {
+    if other.base_url.is_some() {
+      self.base_url = other.base_url;
+    }
+    if other.paths.is_some() {
+      self.paths = other.paths;
+    }
+  }
+}
+
+/// Load `baseUrl` and `paths` from `tsconfig`, following `extends`
+pub fn load_tsconfig(root: &Path, tsconfig: &Path) -> Result<TsconfigPaths> {
+  let mut visited = Vec::new();
+  let options = read_compiler_options(&tsconfig.clean(), &mut visited)?;
+
+  let (paths, paths_dir) = match options.paths {
+    Some((paths, dir)) => (paths, Some(dir)),
+    None => (Vec::new(), None),
+  };
+
+  let paths_base = options
+    .base_url
+    .clone()
+    .or(paths_dir)
+    .unwrap_or_else(|| root.to_path_buf());
+
+  Ok(TsconfigPaths {
+    base_url: options.base_url.map(|p| relative_to_root(&p, root)),
+    paths,
+    paths_base: relative_to_root(&paths_base, root),
+  })
+}
+
+fn read_compiler_options(path: &Path, visited: &mut Vec<PathBuf>) -> Result<RawCompilerOptions> {
+  if visited.iter().any(|p| p == path) {
+    bail!("Circular tsconfig extends: {}", path.display());
+  }
+  visited.push(path.to_path_buf());
+
+  let content =
+    fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+  let json: Value = serde_json::from_str(&strip_jsonc(&content))
+    .with_context(|| format!("Failed to parse {}", path.display()))?;
+  let dir = path.parent().unwrap_or(Path::new(""));
+
+  let mut options = RawCompilerOptions::default();
+
+  // `extends` may be a string or, since TS 5.0, an array where later entries win
+  let extends: Vec<&str> = match json.get("extends") {
+    Some(Value::String(s)) => vec![s.as_str()],
+    Some(Value::Array(items)) => items.iter().filter_map(|v| v.as_str()).collect(),
+    _ => Vec::new(),
+  };
+
+  for spec in extends {
+    let base_path = resolve_extends(dir, spec)
+      .with_context(|| format!("Cannot resolve \"{}\" extended by {}", spec, path.display()))?;
+    options.merge(read_compiler_options(&base_path, visited)?);
+  }
+
+  if let Some(compiler_options) = json.get("compilerOptions") {
+    let mut own = RawCompilerOptions::default();
+
+    if let Some(base_url) = compiler_options.get("baseUrl").and_then(|v| v.as_str()) {
+      own.base_url = Some(dir.join(base_url).clean());
+    }
+
+    if let Some(paths) = compiler_options.get("paths").and_then(|v| v.as_object()) {
+      let mappings = paths
+        .iter()
+        .map(|(pattern, targets)| PathMapping {
+          pattern: pattern.clone(),
+          targets: targets
+            .as_array()
+            .map(|t| {
+              t.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+            })
+            .unwrap_or_default(),
+        })
+        .collect();
+      own.paths = Some((mappings, dir.to_path_buf()));
+    }
+
+    options.merge(own);
+  }
+
+  Ok(options)
+}
+
+/// Resolve an `extends` value: a relative/absolute file or a package in node_modules
+fn resolve_extends(dir: &Path, spec: &str) -> Option<PathBuf> {
+  let with_json = |p: PathBuf| -> Option<PathBuf> {
+    if p.is_file() {
+      return Some(p);
+    }
+    let mut json = p.into_os_string();
+    json.push(".json");
+    let json = PathBuf::from(json);
+    json.is_file().then_some(json)
+  };
+
+  if spec.starts_with('.') || Path::new(spec).is_absolute() {
+    return with_json(dir.join(spec).clean());
+  }
+
+  for ancestor in dir.ancestors() {
+    let candidate = ancestor.join("node_modules").join(spec);
+
+    if let Some(file) = with_json(candidate.clone()) {
+      return Some(file);
+    }
+
+    if candidate.is_dir() {
+      let tsconfig_field = fs::read_to_string(candidate.join("package.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+        .and_then(|pkg| {
+          pkg
+            .get("tsconfig")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+        });
+
+      let file = match tsconfig_field {
+        Some(field) => candidate.join(field),
+        None => candidate.join("tsconfig.json"),
+      };
+      if file.is_file() {
+        return Some(file);
+      }
+    }
+  }
+
+  None
+}
+
+fn relative_to_root(path: &Path, root: &Path) -> PathBuf {
+  let root = root.clean();
+  if root == Path::new(".") {
+    return path.clean();
+  }
+  path
+    .strip_prefix(&root)
+    .map(|p| p.to_path_buf())
+    .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// tsconfig files are JSONC: drop comments and trailing commas so serde_json accepts them
+fn strip_jsonc(input: &str) -> String {
+  let chars: Vec<char> = input.chars().collect();
+  let mut out = String::with_capacity(input.len());
+  let mut i = 0;
+
+  while i < chars.len() {
+    let c = chars[i];
+
+    if c == '"' {
+      out.push(c);
+      i += 1;
+      while i < chars.len() {
+        out.push(chars[i]);
+        if chars[i] == '\\' && i + 1 < chars.len() {
+          out.push(chars[i + 1]);
+          i += 2;
+          continue;
+        }
+        i += 1;
+        if chars[i - 1] == '"' {
+          break;
+        }
+      }
+      continue;
+    }
+
+    if c == '/' && chars.get(i + 1) == Some(&'/') {
+      while i < chars.len() && chars[i] != '\n' {
+        i += 1;
+      }
+      continue;
+    }
+
+    if c == '/' && chars.get(i + 1) == Some(&'*') {
+      i += 2;
+      while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+        i += 1;
+      }
+      i += 2;
+      continue;
+    }
+
+    if c == ',' {
+      let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+      if matches!(next, Some('}') | Some(']')) {
+        i += 1;
+        continue;
+      }
+    }
+
+    out.push(c);
+    i += 1;
+  }
+
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Temporary directory removed on drop
+  struct TempDir(PathBuf);
+
+  impl TempDir {
+    fn new(name: &str) -> Self {
+      let dir =
+        std::env::temp_dir().join(format!("sweepy-tsconfig-{}-{}", name, std::process::id()));
+      let _ = fs::remove_dir_all(&dir);
+      fs::create_dir_all(&dir).unwrap();
+      Self(dir)
+    }
+
+    fn write(&self, path: &str, content: &str) {
+      let p = self.0.join(path);
+      fs::create_dir_all(p.parent().unwrap()).unwrap();
+      fs::write(p, content).unwrap();
+    }
+  }
+
+  impl Drop for TempDir {
+    fn drop(&mut self) {
+      let _ = fs::remove_dir_all(&self.0);
+    }
+  }
+
+  fn mapping(pattern: &str, targets: &[&str]) -> PathMapping {
+    PathMapping {
+      pattern: pattern.to_string(),
+      targets: targets.iter().map(|t| t.to_string()).collect(),
+    }
+  }
+
+  #[test]
+  fn strips_comments_and_trailing_commas() {
+    let input = r#"{
+      // line comment
+      "a": "http://not-a-comment", /* block */
+      "b": [1, 2,],
+    }"#;
+    let json: Value = serde_json::from_str(&strip_jsonc(input)).unwrap();
+    assert_eq!(json["a"], "http://not-a-comment");
+    assert_eq!(json["b"].as_array().unwrap().len(), 2);
+  }
+
+  #[test]
+  fn exact_pattern_wins_over_wildcard() {
+    let ts = TsconfigPaths {
+      base_url: None,
+      paths: vec![
+        mapping("@app/*", &["src/*"]),
+        mapping("@app/config", &["config/index"]),
+      ],
+      paths_base: PathBuf::new(),
+    };
+    assert_eq!(
+      ts.candidates("@app/config"),
+      vec![PathBuf::from("config/index")]
+    );
+    assert_eq!(
+      ts.candidates("@app/utils"),
+      vec![PathBuf::from("src/utils")]
+    );
+  }
+
+  #[test]
+  fn longest_prefix_wins_and_keeps_fallbacks() {
+    let ts = TsconfigPaths {
+      base_url: Some(PathBuf::from("app")),
+      paths: vec![
+        mapping("*", &["types/*"]),
+        mapping("@/*", &["src/*", "generated/*"]),
+      ],
+      paths_base: PathBuf::from("app"),
+    };
+    assert_eq!(
+      ts.candidates("@/api"),
+      vec![
+        PathBuf::from("app/src/api"),
+        PathBuf::from("app/generated/api"),
+        PathBuf::from("app/@/api"),
+      ]
+    );
+  }
+
+  #[test]
+  fn follows_extends_chain() {
+    let dir = TempDir::new("extends");
+    dir.write(
+      "tsconfig.json",
+      r#"{ "extends": ["./configs/base", "@company/tsconfig"], "compilerOptions": { "strict": true } }"#,
+    );
+    dir.write(
+      "configs/base.json",
+      r#"{ "compilerOptions": { "baseUrl": "..", "paths": { "@/*": ["src/*"] } } }"#,
+    );
+    dir.write(
+      "node_modules/@company/tsconfig/package.json",
+      r#"{ "name": "@company/tsconfig", "tsconfig": "shared.json" }"#,
+    );
+    dir.write(
+      "node_modules/@company/tsconfig/shared.json",
+      r#"{ "compilerOptions": { "paths": { "~/*": ["lib/*"] } } }"#,
+    );
+
+    let ts = load_tsconfig(&dir.0, &dir.0.join("tsconfig.json")).unwrap();
+
+    assert_eq!(ts.base_url, Some(PathBuf::new()));
+    assert_eq!(ts.paths.len(), 1);
+    assert_eq!(ts.paths[0].pattern, "~/*");
+    // baseUrl set by the first base, so `paths` from the package resolve against it
+    assert_eq!(ts.candidates("~/x")[0], PathBuf::from("lib/x"));
+  }
+
+  #[test]
+  fn paths_without_base_url_are_relative_to_declaring_config() {
+    let dir = TempDir::new("no-base-url");
+    dir.write(
+      "tsconfig.json",
+      r#"{ "extends": "./packages/app/tsconfig.base.json" }"#,
+    );
+    dir.write(
+      "packages/app/tsconfig.base.json",
+      r##"{ "compilerOptions": { "paths": { "#lib": ["./lib/index.ts"] } } }"##,
+    );
+
+    let ts = load_tsconfig(&dir.0, &dir.0.join("tsconfig.json")).unwrap();
+
+    assert_eq!(ts.base_url, None);
+    assert_eq!(
+      ts.candidates("#lib"),
+      vec![PathBuf::from("packages/app/lib/index.ts")]
+    );
+  }
+}